@@ -0,0 +1,72 @@
+//! Compare the speed of interpreting and compiling an ALU program by
+//! checking random model numbers with both.
+//!
+//! Usage: cargo run --release --example alu_bench [program] [count]
+//!
+//! The program defaults to the day 24 puzzle input.
+
+use aoc_2021::y2021::day24::Program;
+
+use std::{env, fs, process, time::Instant};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = args
+        .next()
+        .unwrap_or_else(|| "input/2021/day24.txt".to_string());
+    let count: usize = match args.next().map(|count| count.parse()) {
+        None => 1_000_000,
+        Some(Ok(count)) => count,
+        Some(Err(err)) => fail(format!("invalid count: {err}")),
+    };
+    let input = fs::read_to_string(&path).unwrap_or_else(|err| fail(format!("{path}: {err}")));
+    let program = Program::parse(&input).unwrap_or_else(|err| fail(format!("{path}: {err}")));
+
+    let start = Instant::now();
+    let compiled = program.compile();
+    println!(
+        "compiled {} instructions to {} in {:.2?}",
+        program.instrs().len(),
+        compiled.ops().len(),
+        start.elapsed()
+    );
+
+    // A fixed xorshift sequence so runs are comparable.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let numbers: Vec<u64> = (0..count)
+        .map(|_| {
+            (0..14).fold(0, |n, _| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                n * 10 + state % 9 + 1
+            })
+        })
+        .collect();
+
+    let start = Instant::now();
+    let interpreted: Vec<_> = numbers.iter().map(|&n| program.accepts(n)).collect();
+    let interpreted_time = start.elapsed();
+    let start = Instant::now();
+    let compiled: Vec<_> = numbers.iter().map(|&n| compiled.accepts(n)).collect();
+    let compiled_time = start.elapsed();
+
+    if interpreted != compiled {
+        fail("interpreted and compiled results differ".to_string());
+    }
+    let accepted = compiled
+        .iter()
+        .filter(|&result| result == &Ok(true))
+        .count();
+    println!("checked {count} model numbers, {accepted} accepted");
+    println!("interpreted: {interpreted_time:.2?}");
+    println!(
+        "compiled:    {compiled_time:.2?} ({:.1}x)",
+        interpreted_time.as_secs_f64() / compiled_time.as_secs_f64()
+    );
+}
+
+fn fail(msg: String) -> ! {
+    eprintln!("{msg}");
+    process::exit(1);
+}
@@ -0,0 +1,94 @@
+//! Draw the final state of the 2021 puzzles that have one worth looking at,
+//! as text and as PNG and PPM images for attaching to reviews.
+//!
+//! Usage: cargo run --example render [out_dir]
+//!
+//! Inputs are read from `input/2021`. The output directory defaults to
+//! `target/render`.
+
+use aoc_2021::{
+    error::{AocError, Result},
+    render::{self, Raster, Rgb, BLACK, WHITE},
+    y2021::{
+        day13,
+        day25::{self, LocationState},
+    },
+};
+
+use std::{env, fs, io, path::Path, process};
+
+/// Each cell becomes a square this many pixels across.
+const SCALE: usize = 8;
+
+fn main() {
+    let out_dir = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "target/render".to_string());
+    if let Err(err) = render_all(Path::new(&out_dir)) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn render_all(out_dir: &Path) -> Result<()> {
+    fs::create_dir_all(out_dir).map_err(|err| io_error(out_dir, err))?;
+
+    let paper = day13::folded_paper(&read_input(13)?)?;
+    save(
+        out_dir,
+        "day13",
+        &paper,
+        |&dot| if dot { '#' } else { '.' },
+        |&dot| if dot { WHITE } else { BLACK },
+    )?;
+
+    let (_, sea_floor) = day25::settle(&read_input(25)?)?;
+    save(
+        out_dir,
+        "day25",
+        &sea_floor,
+        |location| location.to_char(),
+        herd_color,
+    )
+}
+
+/// Write `raster` as `<name>.txt`, `<name>.png` and `<name>.ppm`.
+fn save<R: Raster>(
+    out_dir: &Path,
+    name: &str,
+    raster: &R,
+    to_char: impl Fn(&R::Cell) -> char,
+    to_color: impl Fn(&R::Cell) -> Rgb + Copy,
+) -> Result<()> {
+    let text = render::to_ascii(raster, to_char);
+    write(out_dir, &format!("{name}.txt"), text.as_bytes())?;
+    let png = render::to_png(raster, SCALE, to_color);
+    write(out_dir, &format!("{name}.png"), &png)?;
+    let ppm = render::to_ppm(raster, SCALE, to_color);
+    write(out_dir, &format!("{name}.ppm"), &ppm)
+}
+
+fn herd_color(location: &LocationState) -> Rgb {
+    match location {
+        LocationState::East => [0xe0, 0x60, 0x20],
+        LocationState::South => [0x20, 0x80, 0xe0],
+        LocationState::Empty => BLACK,
+    }
+}
+
+fn read_input(day: u8) -> Result<String> {
+    let path = format!("input/2021/day{day}.txt");
+    let input = fs::read_to_string(&path).map_err(|err| io_error(Path::new(&path), err))?;
+    Ok(input.trim_end().to_string())
+}
+
+fn write(out_dir: &Path, name: &str, contents: &[u8]) -> Result<()> {
+    let path = out_dir.join(name);
+    fs::write(&path, contents).map_err(|err| io_error(&path, err))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn io_error(path: &Path, err: io::Error) -> AocError {
+    AocError::new(format!("{}: {err}", path.display()))
+}
@@ -0,0 +1,421 @@
+//! Run solutions from the command line and check them against known answers.
+//!
+//! Inputs are read from `input/<year>/day<day>.txt`, or from stdin with
+//! `--stdin`. Answers are compared against `answers.toml`, which holds one
+//! table per day:
+//!
+//! ```toml
+//! [2021.day1]
+//! part1 = "1564"
+//! part2 = "1611"
+//! ```
+
+use aoc_2021::{
+    error::{AocError, Result},
+    registry::{self, Solution},
+};
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+const USAGE: &str = "\
+usage: aoc [OPTIONS]
+
+Options:
+  -y, --year LIST      years to run, e.g. 2021 or 2021,2025 (default: all)
+  -d, --day LIST       days to run, e.g. 1,3,20-25 (default: all)
+  -p, --part LIST      parts to run, 1 or 2 (default: both)
+      --stdin          read the input from stdin; requires a single day
+      --input-dir DIR  directory containing <year>/day<day>.txt (default: input)
+      --answers FILE   answers to check against (default: answers.toml)
+      --save           record the answers produced in the answers file
+  -h, --help           print this message";
+
+/// Expected answers keyed by year, day and part.
+type Answers = BTreeMap<(u16, u8, u8), String>;
+
+#[derive(Debug)]
+struct Args {
+    years: Option<Vec<u16>>,
+    days: Option<Vec<u16>>,
+    parts: Option<Vec<u16>>,
+    stdin: bool,
+    input_dir: PathBuf,
+    answers: PathBuf,
+    save: bool,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>> {
+        let mut parsed = Args {
+            years: None,
+            days: None,
+            parts: None,
+            stdin: false,
+            input_dir: PathBuf::from("input"),
+            answers: PathBuf::from("answers.toml"),
+            save: false,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| AocError::new(format!("{arg} requires a value")))
+            };
+            match arg.as_str() {
+                "-y" | "--year" => parsed.years = Some(parse_list(&value()?)?),
+                "-d" | "--day" => parsed.days = Some(parse_list(&value()?)?),
+                "-p" | "--part" => parsed.parts = Some(parse_list(&value()?)?),
+                "--stdin" => parsed.stdin = true,
+                "--input-dir" => parsed.input_dir = value()?.into(),
+                "--answers" => parsed.answers = value()?.into(),
+                "--save" => parsed.save = true,
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(AocError::new(format!("unknown argument {arg:?}"))),
+            }
+        }
+        Ok(Some(parsed))
+    }
+
+    fn selects(&self, solution: &Solution) -> bool {
+        let matches =
+            |list: &Option<Vec<u16>>, n: u16| list.as_ref().is_none_or(|l| l.contains(&n));
+        matches(&self.years, solution.year)
+            && matches(&self.days, solution.day.into())
+            && matches(&self.parts, solution.part.into())
+    }
+}
+
+/// Parse a comma separated list of numbers and inclusive ranges like `3-5`.
+fn parse_list(s: &str) -> Result<Vec<u16>> {
+    let number = |n: &str| {
+        n.trim()
+            .parse::<u16>()
+            .map_err(|_| AocError::new(format!("invalid number {n:?} in {s:?}")))
+    };
+    let mut list = Vec::new();
+    for item in s.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (number(start)?, number(end)?);
+                if start > end {
+                    return Err(AocError::new(format!(
+                        "empty range {:?} in {s:?}",
+                        item.trim()
+                    )));
+                }
+                list.extend(start..=end);
+            }
+            None => list.push(number(item)?),
+        }
+    }
+    Ok(list)
+}
+
+/// Read the answers file. A missing file is treated as having no answers.
+fn load_answers(path: &Path) -> Result<Answers> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::new()),
+        Err(err) => return Err(AocError::new(format!("{}: {err}", path.display()))),
+    };
+    parse_answers(&text).map_err(|err| AocError::new(format!("{}: {err}", path.display())))
+}
+
+/// Parse the small subset of TOML written by [`save_answers`]: `[YEAR.dayN]`
+/// tables of `partN = "answer"` keys, where answers are basic strings with
+/// TOML escapes. Bare integers are accepted as values too.
+fn parse_answers(text: &str) -> Result<Answers> {
+    let mut answers = Answers::new();
+    let mut table = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let (year, day) = header
+                .strip_suffix(']')
+                .and_then(|header| header.split_once(".day"))
+                .ok_or_else(|| AocError::at(text, line, "expected a [YEAR.dayN] table"))?;
+            table = Some((
+                year.parse()
+                    .map_err(|_| AocError::at(text, year, "invalid year"))?,
+                day.parse()
+                    .map_err(|_| AocError::at(text, day, "invalid day"))?,
+            ));
+            continue;
+        }
+        let (year, day) =
+            table.ok_or_else(|| AocError::at(text, line, "answer outside of a table"))?;
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| AocError::at(text, line, "expected 'partN = \"answer\"'"))?;
+        let (key, value) = (key.trim(), value.trim());
+        let part = key
+            .strip_prefix("part")
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(|| AocError::at(text, key, "expected 'part1' or 'part2'"))?;
+        let value = if value.starts_with('"') {
+            unquote(text, value)?
+        } else if value.bytes().all(|b| b.is_ascii_digit() || b == b'-') {
+            value.to_string()
+        } else {
+            return Err(AocError::at(text, value, "expected a string or integer"));
+        };
+        answers.insert((year, day, part), value);
+    }
+    Ok(answers)
+}
+
+/// Read the TOML basic string `value`, which starts with its opening quote.
+/// `text` is the whole file, used to locate errors.
+fn unquote(text: &str, value: &str) -> Result<String> {
+    let mut answer = String::new();
+    let mut chars = value.char_indices().skip(1);
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' if idx + 1 == value.len() => return Ok(answer),
+            '"' => {
+                return Err(AocError::at(
+                    text,
+                    &value[idx + 1..],
+                    "unexpected text after string",
+                ))
+            }
+            '\\' => {
+                let escape = &value[idx..];
+                answer.push(match chars.next().map(|(_, c)| c) {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some(u @ ('u' | 'U')) => {
+                        let len = if u == 'u' { 4 } else { 8 };
+                        let hex: String = chars.by_ref().take(len).map(|(_, c)| c).collect();
+                        Some(hex)
+                            .filter(|hex| {
+                                hex.len() == len && hex.bytes().all(|b| b.is_ascii_hexdigit())
+                            })
+                            .and_then(|hex| u32::from_str_radix(&hex, 16).ok())
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| AocError::at(text, escape, "invalid unicode escape"))?
+                    }
+                    _ => return Err(AocError::at(text, escape, "invalid escape")),
+                });
+            }
+            c => answer.push(c),
+        }
+    }
+    Err(AocError::at(text, value, "unterminated string"))
+}
+
+/// Quote `answer` as a TOML basic string, escaping anything [`unquote`]
+/// wouldn't read back as itself.
+fn quote(answer: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in answer.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn save_answers(path: &Path, answers: &Answers) -> Result<()> {
+    fs::write(path, format_answers(answers))
+        .map_err(|err| AocError::new(format!("{}: {err}", path.display())))
+}
+
+/// The answers file for `answers`, as read back by [`parse_answers`].
+fn format_answers(answers: &Answers) -> String {
+    let mut text = String::new();
+    let mut table = None;
+    for (&(year, day, part), answer) in answers {
+        if table != Some((year, day)) {
+            if table.is_some() {
+                text.push('\n');
+            }
+            text.push_str(&format!("[{year}.day{day}]\n"));
+            table = Some((year, day));
+        }
+        text.push_str(&format!("part{part} = {}\n", quote(answer)));
+    }
+    text
+}
+
+fn run(args: &Args) -> Result<bool> {
+    let solutions: Vec<_> = registry::all().filter(|s| args.selects(s)).collect();
+    if solutions.is_empty() {
+        return Err(AocError::new("no solutions match the selection"));
+    }
+    let stdin_input = if args.stdin {
+        if solutions
+            .iter()
+            .any(|s| (s.year, s.day) != (solutions[0].year, solutions[0].day))
+        {
+            return Err(AocError::new("--stdin requires selecting a single day"));
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|err| AocError::new(format!("reading stdin: {err}")))?;
+        Some(input)
+    } else {
+        None
+    };
+
+    let mut answers = load_answers(&args.answers)?;
+    let (mut num_ok, mut num_wrong, mut num_failed, mut num_skipped) = (0, 0, 0, 0);
+    for solution in solutions {
+        let label = format!(
+            "{} day {:>2} part {}",
+            solution.year, solution.day, solution.part
+        );
+        let input = match &stdin_input {
+            Some(input) => input.clone(),
+            None => {
+                let path = args
+                    .input_dir
+                    .join(solution.year.to_string())
+                    .join(format!("day{}.txt", solution.day));
+                match fs::read_to_string(&path) {
+                    Ok(input) => input,
+                    Err(err) => {
+                        println!("{label}  skipped: {}: {err}", path.display());
+                        num_skipped += 1;
+                        continue;
+                    }
+                }
+            }
+        };
+
+        let start = Instant::now();
+        let result = solution.run(&input);
+        let elapsed = start.elapsed();
+        let answer = match result {
+            Ok(answer) => answer,
+            Err(err) => {
+                println!("{label}  error: {err}  ({elapsed:.2?})");
+                num_failed += 1;
+                continue;
+            }
+        };
+        let key = (solution.year, solution.day, solution.part);
+        let status = match answers.get(&key) {
+            Some(expected) if *expected == answer => {
+                num_ok += 1;
+                "ok".to_string()
+            }
+            Some(expected) => {
+                num_wrong += 1;
+                format!("WRONG, expected {expected}")
+            }
+            None => "new".to_string(),
+        };
+        println!("{label}  {answer:<20} ({elapsed:.2?})  {status}");
+        if args.save {
+            answers.insert(key, answer);
+        }
+    }
+
+    println!("\n{num_ok} ok, {num_wrong} wrong, {num_failed} failed, {num_skipped} skipped");
+    if args.save {
+        save_answers(&args.answers, &answers)?;
+    }
+    Ok(num_wrong == 0 && num_failed == 0)
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[test]
+fn test_parse_list() {
+    assert_eq!(parse_list("1,3-5, 7").unwrap(), [1, 3, 4, 5, 7]);
+    assert_eq!(parse_list("25").unwrap(), [25]);
+    assert_eq!(
+        parse_list("5-3").unwrap_err().to_string(),
+        "empty range \"5-3\" in \"5-3\""
+    );
+    assert_eq!(
+        parse_list("1,x").unwrap_err().to_string(),
+        "invalid number \"x\" in \"1,x\""
+    );
+}
+
+#[test]
+fn test_parse_answers() {
+    let text = "# Known answers\n\
+                [2021.day1]\n\
+                part1 = \"1446\"\n\
+                part2 = 1486\n\
+                \n\
+                [2021.day13]\n\
+                part2 = \"#..#\\n\\\"\\u00e9\\\\\"\n";
+    let answers = parse_answers(text).unwrap();
+    assert_eq!(answers[&(2021, 1, 1)], "1446");
+    assert_eq!(answers[&(2021, 1, 2)], "1486");
+    assert_eq!(answers[&(2021, 13, 2)], "#..#\n\"\u{e9}\\");
+
+    // Whatever is saved reads back the same.
+    let mut answers = Answers::new();
+    answers.insert((2021, 13, 2), "#..#\n#\t\"\\\u{1b}\r".to_string());
+    answers.insert((2021, 25, 1), "42".to_string());
+    assert_eq!(parse_answers(&format_answers(&answers)).unwrap(), answers);
+
+    let err = |text| parse_answers(text).unwrap_err().to_string();
+    assert_eq!(
+        err("[2021.day1]\npart1 = \"12"),
+        "line 2, column 9: unterminated string"
+    );
+    assert_eq!(
+        err("[2021.day1]\npart1 = \"1\\q\""),
+        "line 2, column 11: invalid escape"
+    );
+    assert_eq!(
+        err("[2021.day1]\npart1 = \"1\\u12\""),
+        "line 2, column 11: invalid unicode escape"
+    );
+    assert_eq!(
+        err("[2021.day1]\npart1 = \"1\" 2"),
+        "line 2, column 12: unexpected text after string"
+    );
+    assert_eq!(
+        err("part1 = \"1\""),
+        "line 1, column 1: answer outside of a table"
+    );
+}
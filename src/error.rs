@@ -0,0 +1,83 @@
+//! The error type shared by every puzzle parser.
+
+use std::fmt;
+
+/// `Result` defaulting to [`AocError`]. The error type can still be
+/// overridden, which keeps this usable inside code generated by aoc-runner.
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    msg: String,
+    pos: Option<Pos>,
+}
+
+/// A 1-based line and column within the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Pos {
+    /// The position of the byte at `offset` within `input`.
+    pub fn from_offset(input: &str, offset: usize) -> Self {
+        let before = &input.as_bytes()[..offset.min(input.len())];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        Pos {
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: before.len() - line_start + 1,
+        }
+    }
+}
+
+impl AocError {
+    /// An error that can't be attributed to a location in the input.
+    pub fn new(msg: impl Into<String>) -> Self {
+        AocError {
+            msg: msg.into(),
+            pos: None,
+        }
+    }
+
+    /// An error located at `fragment`, which should be a subslice of `input`.
+    /// If it isn't the error is reported without a location.
+    pub fn at(input: &str, fragment: &str, msg: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        if offset <= input.len() {
+            AocError::at_offset(input, offset, msg)
+        } else {
+            AocError::new(msg)
+        }
+    }
+
+    /// An error located at the byte `offset` within `input`.
+    pub fn at_offset(input: &str, offset: usize, msg: impl Into<String>) -> Self {
+        AocError {
+            msg: msg.into(),
+            pos: Some(Pos::from_offset(input, offset)),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn pos(&self) -> Option<Pos> {
+        self.pos
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pos {
+            Some(Pos { line, column }) => write!(f, "line {line}, column {column}: {}", self.msg),
+            None => write!(f, "{}", self.msg),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
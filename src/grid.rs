@@ -1,11 +1,16 @@
-use std::ops::{Index, IndexMut};
+use crate::error::{AocError, Result};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Index, IndexMut},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Y(pub usize);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct X(pub usize);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
@@ -24,6 +29,50 @@ impl<T> Grid<T> {
         Grid { data, width }
     }
 
+    /// Parse a grid with one cell per byte of input, converting each byte with
+    /// `f`. Every line must be the same length. Lines may end with either
+    /// `\n` or `\r\n`, and so may the last one. Errors returned by `f` are
+    /// reported at the position of the byte being converted.
+    pub fn parse_chars_with(input: &str, mut f: impl FnMut(u8) -> Result<T>) -> Result<Self> {
+        let mut data = Vec::with_capacity(input.len());
+        let mut width = None;
+        let lines = input.strip_suffix('\n').unwrap_or(input);
+        for line in lines.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            match width {
+                None => width = Some(line.len()),
+                Some(width) if width != line.len() => {
+                    return Err(AocError::at(
+                        input,
+                        line,
+                        format!("expected {width} columns, found {}", line.len()),
+                    ));
+                }
+                Some(_) => {}
+            }
+            let line_offset = line.as_ptr() as usize - input.as_ptr() as usize;
+            for (idx, b) in line.bytes().enumerate() {
+                data.push(
+                    f(b).map_err(|err| {
+                        AocError::at_offset(input, line_offset + idx, err.message())
+                    })?,
+                );
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid::new(data, width)),
+            _ => Err(AocError::new("empty grid")),
+        }
+    }
+
+    /// Convert every cell with `f`, keeping the same dimensions.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.into_iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
     pub fn height(&self) -> usize {
         self.data.len() / self.width
     }
@@ -37,35 +86,60 @@ impl<T> Grid<T> {
     }
 
     pub fn neighbors_4(&self, x: X, y: Y) -> AdjacentIter {
-        AdjacentIter {
-            x,
-            y,
-            height: self.data.len() / self.width,
-            width: self.width,
-            states: NEIGHBORS_8[0..4].iter().copied(),
-        }
+        self.adjacent(x, y, &NEIGHBORS_8[0..4], false)
     }
 
     pub fn neighbors_diag(&self, x: X, y: Y) -> AdjacentIter {
-        AdjacentIter {
-            x,
-            y,
-            height: self.data.len() / self.width,
-            width: self.width,
-            states: NEIGHBORS_8[4..8].iter().copied(),
-        }
+        self.adjacent(x, y, &NEIGHBORS_8[4..8], false)
     }
 
     pub fn neighbors_8(&self, x: X, y: Y) -> AdjacentIter {
+        self.adjacent(x, y, NEIGHBORS_8, false)
+    }
+
+    /// Like [`Grid::neighbors_4`], but treats the grid as a torus: stepping
+    /// off one edge comes back in on the opposite edge. On grids only one or
+    /// two cells across, each neighbor is still returned once, and a cell is
+    /// never its own neighbor.
+    pub fn neighbors_4_wrapping(&self, x: X, y: Y) -> AdjacentIter {
+        self.adjacent(x, y, &NEIGHBORS_8[0..4], true)
+    }
+
+    /// Like [`Grid::neighbors_diag`], but wrapping around the edges.
+    pub fn neighbors_diag_wrapping(&self, x: X, y: Y) -> AdjacentIter {
+        self.adjacent(x, y, &NEIGHBORS_8[4..8], true)
+    }
+
+    /// Like [`Grid::neighbors_8`], but wrapping around the edges.
+    pub fn neighbors_8_wrapping(&self, x: X, y: Y) -> AdjacentIter {
+        self.adjacent(x, y, NEIGHBORS_8, true)
+    }
+
+    fn adjacent(&self, x: X, y: Y, directions: &'static [Direction], wrap: bool) -> AdjacentIter {
         AdjacentIter {
             x,
             y,
-            height: self.data.len() / self.width,
+            height: self.height(),
             width: self.width,
-            states: NEIGHBORS_8.iter().copied(),
+            wrap,
+            directions,
+            next: 0,
         }
     }
 
+    /// The position one step from `pos` in direction `dir`, or `None` if
+    /// that would leave the grid.
+    pub fn step(&self, pos: (X, Y), dir: Direction) -> Option<(X, Y)> {
+        dir.step(pos, self.width, self.height(), false)
+    }
+
+    /// The position one step from `pos` in direction `dir`, wrapping around
+    /// to the opposite edge when stepping off the grid.
+    pub fn step_wrapping(&self, pos: (X, Y), dir: Direction) -> (X, Y) {
+        dir.step(pos, self.width, self.height(), true)
+            .expect("wrapping steps stay on the grid")
+    }
+
     pub fn points_values(&self) -> impl Iterator<Item = ((X, Y), &T)> + '_ {
         self.data.iter().enumerate().map(|(idx, v)| {
             let x = X(idx % self.width);
@@ -75,108 +149,92 @@ impl<T> Grid<T> {
     }
 }
 
+impl Grid<u8> {
+    /// Parse a grid where every cell is a single decimal digit.
+    pub fn parse_digits(input: &str) -> Result<Self> {
+        Grid::parse_chars_with(input, |b| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(AocError::new("expected a digit"))
+            }
+        })
+    }
+}
+
 impl<T> Index<(X, Y)> for Grid<T> {
     type Output = T;
 
     fn index(&self, (x, y): (X, Y)) -> &Self::Output {
-        assert!(y.0 < self.width);
+        assert!(x.0 < self.width && y.0 < self.height());
         &self.data[y.0 * self.width + x.0]
     }
 }
 
 impl<T> IndexMut<(X, Y)> for Grid<T> {
     fn index_mut(&mut self, (x, y): (X, Y)) -> &mut Self::Output {
-        assert!(y.0 < self.width);
+        assert!(x.0 < self.width && y.0 < self.height());
         &mut self.data[y.0 * self.width + x.0]
     }
 }
 
-const NEIGHBORS_8: &[AdjacentIterState] = &[
-    AdjacentIterState::Above,
-    AdjacentIterState::Left,
-    AdjacentIterState::Below,
-    AdjacentIterState::Right,
-    AdjacentIterState::AboveLeft,
-    AdjacentIterState::BelowLeft,
-    AdjacentIterState::BelowRight,
-    AdjacentIterState::AboveRight,
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.data.chunks(self.width) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+const NEIGHBORS_8: &[Direction] = &[
+    Direction::Above,
+    Direction::Left,
+    Direction::Below,
+    Direction::Right,
+    Direction::AboveLeft,
+    Direction::BelowLeft,
+    Direction::BelowRight,
+    Direction::AboveRight,
 ];
 
 #[derive(Debug)]
 pub struct AdjacentIter {
-    states: std::iter::Copied<std::slice::Iter<'static, AdjacentIterState>>,
+    directions: &'static [Direction],
+    next: usize,
     x: X,
     y: Y,
     height: usize,
     width: usize,
+    wrap: bool,
 }
 impl Iterator for AdjacentIter {
     type Item = (X, Y);
     fn next(&mut self) -> Option<Self::Item> {
-        match self.states.next() {
-            None => None,
-            Some(AdjacentIterState::Above) => {
-                if self.y.0 > 0 {
-                    Some((self.x, Y(self.y.0 - 1)))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::AboveLeft) => {
-                if self.y.0 > 0 && self.x.0 > 0 {
-                    Some((X(self.x.0 - 1), Y(self.y.0 - 1)))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::Left) => {
-                if self.x.0 > 0 {
-                    Some((X(self.x.0 - 1), self.y))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::BelowLeft) => {
-                if self.x.0 > 0 && self.y.0 + 1 < self.height {
-                    Some((X(self.x.0 - 1), Y(self.y.0 + 1)))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::Below) => {
-                if self.y.0 + 1 < self.height {
-                    Some((self.x, Y(self.y.0 + 1)))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::BelowRight) => {
-                if self.y.0 + 1 < self.height && self.x.0 + 1 < self.width {
-                    Some((X(self.x.0 + 1), Y(self.y.0 + 1)))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::Right) => {
-                if self.x.0 + 1 < self.width {
-                    Some((X(self.x.0 + 1), self.y))
-                } else {
-                    self.next()
-                }
-            }
-            Some(AdjacentIterState::AboveRight) => {
-                if self.x.0 + 1 < self.width && self.y.0 > 0 {
-                    Some((X(self.x.0 + 1), Y(self.y.0 - 1)))
-                } else {
-                    self.next()
-                }
+        let (x, y, width, height, wrap) = (self.x, self.y, self.width, self.height, self.wrap);
+        let step = |dir: &Direction| dir.step((x, y), width, height, wrap);
+        while let Some(dir) = self.directions.get(self.next) {
+            let earlier = &self.directions[..self.next];
+            self.next += 1;
+            let Some(pos) = step(dir) else {
+                continue;
+            };
+            // Wrapping around a grid only one or two cells across can lead
+            // back to the cell itself, or to a neighbor already returned.
+            if wrap && (pos == (x, y) || earlier.iter().any(|dir| step(dir) == Some(pos))) {
+                continue;
             }
+            return Some(pos);
         }
+        None
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum AdjacentIterState {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
     Above,
     AboveLeft,
     Left,
@@ -186,3 +244,274 @@ enum AdjacentIterState {
     Right,
     AboveRight,
 }
+
+impl Direction {
+    /// The change in x and y when moving one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Above => (0, -1),
+            Direction::AboveLeft => (-1, -1),
+            Direction::Left => (-1, 0),
+            Direction::BelowLeft => (-1, 1),
+            Direction::Below => (0, 1),
+            Direction::BelowRight => (1, 1),
+            Direction::Right => (1, 0),
+            Direction::AboveRight => (1, -1),
+        }
+    }
+
+    fn step(self, (x, y): (X, Y), width: usize, height: usize, wrap: bool) -> Option<(X, Y)> {
+        let (dx, dy) = self.delta();
+        Some((
+            X(offset(x.0, dx, width, wrap)?),
+            Y(offset(y.0, dy, height, wrap)?),
+        ))
+    }
+}
+
+fn offset(n: usize, delta: isize, len: usize, wrap: bool) -> Option<usize> {
+    if wrap {
+        Some((n + len).checked_add_signed(delta)? % len)
+    } else {
+        n.checked_add_signed(delta).filter(|&n| n < len)
+    }
+}
+
+/// An unbounded grid addressed by signed coordinates. Every cell holds the
+/// background value until set to something else; only cells that differ
+/// from the background are stored.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    background: T,
+    bounds: Option<Bounds>,
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    pub fn new(background: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            background,
+            bounds: None,
+        }
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, pos: (isize, isize)) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+
+    /// Set the cell at `pos`. Setting a cell to the background value removes
+    /// it from the grid.
+    pub fn set(&mut self, pos: (isize, isize), value: T) {
+        if value == self.background {
+            if self.cells.remove(&pos).is_some()
+                && self.bounds.is_some_and(|bounds| bounds.on_edge(pos))
+            {
+                self.bounds = Bounds::enclosing(self.cells.keys().copied());
+            }
+        } else {
+            self.cells.insert(pos, value);
+            self.bounds = Some(match self.bounds {
+                Some(bounds) => bounds.including(pos),
+                None => Bounds::point(pos),
+            });
+        }
+    }
+
+    /// The number of cells that differ from the background.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest box containing every cell that differs from the
+    /// background, or `None` if there are no such cells.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// The cells that differ from the background, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> + '_ {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+}
+
+/// An axis-aligned box of signed coordinates. Both ends of each range are
+/// inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min_x: isize,
+    pub max_x: isize,
+    pub min_y: isize,
+    pub max_y: isize,
+}
+
+impl Bounds {
+    pub fn point((x, y): (isize, isize)) -> Self {
+        Bounds {
+            min_x: x,
+            max_x: x,
+            min_y: y,
+            max_y: y,
+        }
+    }
+
+    /// The smallest box containing every point, or `None` if there are none.
+    pub fn enclosing(points: impl IntoIterator<Item = (isize, isize)>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = Bounds::point(points.next()?);
+        Some(points.fold(first, Bounds::including))
+    }
+
+    /// The smallest box containing both this box and `pos`.
+    pub fn including(self, (x, y): (isize, isize)) -> Self {
+        Bounds {
+            min_x: self.min_x.min(x),
+            max_x: self.max_x.max(x),
+            min_y: self.min_y.min(y),
+            max_y: self.max_y.max(y),
+        }
+    }
+
+    /// Grow the box by `n` cells in every direction.
+    pub fn expand(self, n: isize) -> Self {
+        Bounds {
+            min_x: self.min_x - n,
+            max_x: self.max_x + n,
+            min_y: self.min_y - n,
+            max_y: self.max_y + n,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.min_x.abs_diff(self.max_x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.min_y.abs_diff(self.max_y) + 1
+    }
+
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+    }
+
+    /// Every point within the box, row by row.
+    pub fn points(self) -> impl Iterator<Item = (isize, isize)> {
+        (self.min_y..=self.max_y).flat_map(move |y| (self.min_x..=self.max_x).map(move |x| (x, y)))
+    }
+
+    fn on_edge(&self, (x, y): (isize, isize)) -> bool {
+        x == self.min_x || x == self.max_x || y == self.min_y || y == self.max_y
+    }
+}
+
+#[test]
+fn test_parse_grid() {
+    let grid = Grid::parse_digits("123\r\n456").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(X(2), Y(1))], 6);
+    assert_eq!(grid.to_string(), "123\n456\n");
+    for input in ["123\n456\n", "123\r\n456\r\n"] {
+        let trailing = Grid::parse_digits(input).unwrap();
+        assert_eq!((trailing.width(), trailing.height()), (3, 2));
+    }
+
+    let err = |input| {
+        let err = Grid::parse_digits(input).unwrap_err();
+        let pos = err.pos().map(|pos| (pos.line, pos.column));
+        (err.message().to_string(), pos)
+    };
+    assert_eq!(
+        err("123\n4567\n890"),
+        ("expected 3 columns, found 4".to_string(), Some((2, 1)))
+    );
+    assert_eq!(
+        err("123\r\n45"),
+        ("expected 3 columns, found 2".to_string(), Some((2, 1)))
+    );
+    assert_eq!(
+        err("123\r\n4x6"),
+        ("expected a digit".to_string(), Some((2, 2)))
+    );
+    assert_eq!(
+        err("123\n456\n\n"),
+        ("expected 3 columns, found 0".to_string(), Some((3, 1)))
+    );
+    assert_eq!(err(""), ("empty grid".to_string(), None));
+    assert_eq!(err("\n"), ("empty grid".to_string(), None));
+}
+
+#[test]
+fn test_wrapping() {
+    let grid = Grid::new(vec![0; 6], 3);
+    let neighbors = |iter: AdjacentIter| -> Vec<_> { iter.map(|(x, y)| (x.0, y.0)).collect() };
+    // Each edge of a 3 by 2 grid wraps to the opposite one.
+    assert_eq!(
+        neighbors(grid.neighbors_4_wrapping(X(0), Y(0))),
+        [(0, 1), (2, 0), (1, 0)]
+    );
+    assert_eq!(neighbors(grid.neighbors_4(X(0), Y(0))), [(0, 1), (1, 0)]);
+    assert_eq!(
+        neighbors(grid.neighbors_4_wrapping(X(2), Y(1))),
+        [(2, 0), (1, 1), (0, 1)]
+    );
+    assert_eq!(
+        neighbors(grid.neighbors_diag_wrapping(X(0), Y(0))),
+        [(2, 1), (1, 1)]
+    );
+    assert_eq!(grid.neighbors_8_wrapping(X(1), Y(0)).count(), 5);
+
+    for (pos, dir, wrapped) in [
+        ((0, 1), Direction::Left, (2, 1)),
+        ((2, 0), Direction::Right, (0, 0)),
+        ((1, 0), Direction::Above, (1, 1)),
+        ((1, 1), Direction::Below, (1, 0)),
+        ((0, 0), Direction::AboveLeft, (2, 1)),
+    ] {
+        let pos = (X(pos.0), Y(pos.1));
+        assert_eq!(grid.step(pos, dir), None);
+        assert_eq!(grid.step_wrapping(pos, dir), (X(wrapped.0), Y(wrapped.1)));
+    }
+    assert_eq!(
+        grid.step((X(0), Y(0)), Direction::BelowRight),
+        Some((X(1), Y(1)))
+    );
+
+    // Too small for every direction to lead somewhere new.
+    let grid = Grid::new(vec![0; 4], 2);
+    assert_eq!(
+        neighbors(grid.neighbors_8_wrapping(X(0), Y(0))),
+        [(0, 1), (1, 0), (1, 1)]
+    );
+    let grid = Grid::new(vec![0], 1);
+    assert_eq!(grid.neighbors_8_wrapping(X(0), Y(0)).count(), 0);
+    assert_eq!(
+        grid.step_wrapping((X(0), Y(0)), Direction::Left),
+        (X(0), Y(0))
+    );
+}
+
+#[test]
+fn test_sparse_grid_bounds() {
+    let mut grid = SparseGrid::new('.');
+    assert_eq!(grid.bounds(), None);
+    grid.set((-2, 3), '#');
+    grid.set((4, -1), '#');
+    grid.set((0, 0), '#');
+    assert_eq!(grid.bounds(), Bounds::enclosing([(-2, -1), (4, 3)]));
+    grid.set((4, -1), '.');
+    assert_eq!(grid.bounds(), Bounds::enclosing([(-2, 0), (0, 3)]));
+    assert_eq!(*grid.get((4, -1)), '.');
+    assert_eq!(grid.len(), 2);
+    grid.set((-2, 3), '.');
+    grid.set((0, 0), '.');
+    assert_eq!(grid.bounds(), None);
+    assert!(grid.is_empty());
+}
@@ -1,29 +1,14 @@
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-mod day24;
-mod day25;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+#[macro_use]
+pub mod registry;
 
+pub mod error;
 pub mod grid;
+pub mod ocr;
+pub mod parse;
+pub mod render;
+pub mod search;
+
+pub mod y2021;
+pub mod y2025;
 
 aoc_runner_derive::aoc_lib! { year = 2021 }
@@ -0,0 +1,97 @@
+//! Read text drawn with the block letter font used by several puzzles.
+//!
+//! Each letter is 4 pixels wide and 6 tall, and letters are separated by a
+//! single blank column.
+
+use crate::error::{AocError, Result};
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+const LETTER_SPACING: usize = LETTER_WIDTH + 1;
+
+/// Each letter's pixels, one string per row with `#` for lit pixels.
+const FONT: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The lit pixels of a letter as a bit mask, row by row from the top left.
+fn glyph_mask(rows: &[&str; LETTER_HEIGHT]) -> u32 {
+    rows.iter()
+        .flat_map(|row| row.bytes())
+        .fold(0, |mask, b| mask << 1 | (b == b'#') as u32)
+}
+
+/// Read the letters formed by a set of lit pixels given as `(x, y)`. The
+/// first letter starts at column 0 and the top row is row 0.
+pub fn read_letters(points: impl IntoIterator<Item = (usize, usize)>) -> Result<String> {
+    let mut masks = Vec::new();
+    for (x, y) in points {
+        if y >= LETTER_HEIGHT {
+            return Err(AocError::new(format!(
+                "pixel ({x}, {y}) is below the letters, which are {LETTER_HEIGHT} rows tall"
+            )));
+        }
+        let (letter, column) = (x / LETTER_SPACING, x % LETTER_SPACING);
+        if column == LETTER_WIDTH {
+            return Err(AocError::new(format!(
+                "pixel ({x}, {y}) is in the gap between letters"
+            )));
+        }
+        if letter >= masks.len() {
+            masks.resize(letter + 1, 0);
+        }
+        let bit = LETTER_WIDTH * LETTER_HEIGHT - 1 - (y * LETTER_WIDTH + column);
+        masks[letter] |= 1 << bit;
+    }
+    masks
+        .into_iter()
+        .enumerate()
+        .map(|(idx, mask)| {
+            FONT.iter()
+                .find(|(_, rows)| glyph_mask(rows) == mask)
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| {
+                    AocError::new(format!(
+                        "unrecognized letter starting at column {}",
+                        idx * LETTER_SPACING
+                    ))
+                })
+        })
+        .collect()
+}
+
+#[test]
+fn test_read_letters() {
+    let picture = [
+        "#..#.####.#....####.#..#...##.###..#..#",
+        "#..#....#.#....#....#..#....#.#..#.#.#.",
+        "####...#..#....###..####....#.#..#.##..",
+        "#..#..#...#....#....#..#....#.###..#.#.",
+        "#..#.#....#....#....#..#.#..#.#.#..#.#.",
+        "#..#.####.####.####.#..#..##..#..#.#..#",
+    ];
+    let points = picture.iter().enumerate().flat_map(|(y, row)| {
+        row.bytes()
+            .enumerate()
+            .filter(|&(_, b)| b == b'#')
+            .map(move |(x, _)| (x, y))
+    });
+    assert_eq!(read_letters(points).unwrap(), "HZLEHJRK");
+    assert!(read_letters([(1, 1)]).is_err());
+}
@@ -0,0 +1,41 @@
+//! Small helpers for parsing puzzle input into [`AocError`]s rather than
+//! panicking. Each takes the complete puzzle `input` alongside the fragment
+//! being parsed so that errors can report the line and column they occurred
+//! at.
+
+use crate::error::{AocError, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// Parse `s` with `FromStr`.
+pub fn parse<T>(input: &str, s: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.parse()
+        .map_err(|err| AocError::at(input, s, format!("invalid value {s:?}: {err}")))
+}
+
+/// Like `str::split_once`, but an error if `delim` is missing.
+pub fn split_once<'a>(input: &str, s: &'a str, delim: &str) -> Result<(&'a str, &'a str)> {
+    s.split_once(delim)
+        .ok_or_else(|| AocError::at(input, s, format!("expected {delim:?}")))
+}
+
+/// Like `str::strip_prefix`, but an error if `prefix` is missing.
+pub fn strip_prefix<'a>(input: &str, s: &'a str, prefix: &str) -> Result<&'a str> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| AocError::at(input, s, format!("expected {prefix:?}")))
+}
+
+/// The next item from `iter`. `after` is the fragment that was being split to
+/// produce `iter`; a missing item is reported at its end.
+pub fn next<'a>(
+    input: &str,
+    iter: &mut impl Iterator<Item = &'a str>,
+    after: &str,
+    what: &str,
+) -> Result<&'a str> {
+    iter.next()
+        .ok_or_else(|| AocError::at(input, &after[after.len()..], format!("expected {what}")))
+}
@@ -0,0 +1,52 @@
+//! A registry of every solution in the crate, keyed by year, day and part.
+//!
+//! aoc-runner can only be configured for a single year, so the registry is
+//! what allows solutions from multiple years to live side by side.
+
+/// Register a solver function for the given year, day and part. The solver
+/// must return a `Result` of any type implementing `Display`.
+macro_rules! solution {
+    ($year:literal, $day:literal, $part:literal, $solver:expr) => {
+        $crate::registry::Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            solver: |input| Ok(($solver)(input)?.to_string()),
+        }
+    };
+}
+
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub(crate) solver: fn(&str) -> Result<String>,
+}
+
+impl Solution {
+    /// Run the solution against the provided puzzle input. Trailing newlines
+    /// are stripped the same way aoc-runner does.
+    pub fn run(&self, input: &str) -> Result<String> {
+        (self.solver)(input.trim_end_matches('\n'))
+    }
+}
+
+/// All registered solutions ordered by year, day and part.
+pub fn all() -> impl Iterator<Item = &'static Solution> {
+    crate::y2021::SOLUTIONS
+        .iter()
+        .chain(crate::y2025::SOLUTIONS.iter())
+}
+
+pub fn find(year: u16, day: u8, part: u8) -> Option<&'static Solution> {
+    all().find(|s| s.year == year && s.day == day && s.part == part)
+}
+
+/// Run the solution for the given year, day and part. Returns `None` if no
+/// such solution is registered.
+pub fn run(year: u16, day: u8, part: u8, input: &str) -> Option<Result<String>> {
+    find(year, day, part).map(|s| s.run(input))
+}
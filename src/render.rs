@@ -0,0 +1,290 @@
+//! Draw grids and sets of points as text or images.
+//!
+//! Anything implementing [`Raster`] can be rendered. The caller decides how
+//! each cell looks by passing a function from cells to characters or colors.
+
+use crate::grid::{Bounds, Grid, SparseGrid, X, Y};
+use std::collections::HashSet;
+
+/// A color as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [0xff, 0xff, 0xff];
+
+/// A rectangle of cells that can be rendered. Positions are counted from
+/// the top left corner of the rectangle.
+pub trait Raster {
+    type Cell;
+
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn cell(&self, x: usize, y: usize) -> &Self::Cell;
+}
+
+impl<T> Raster for Grid<T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> &T {
+        &self[(X(x), Y(y))]
+    }
+}
+
+/// Only the region within [`SparseGrid::bounds`] is rendered.
+impl<T: PartialEq> Raster for SparseGrid<T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.bounds().map_or(0, |bounds| bounds.width())
+    }
+
+    fn height(&self) -> usize {
+        self.bounds().map_or(0, |bounds| bounds.height())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> &T {
+        match self.bounds() {
+            Some(bounds) => self.get((bounds.min_x + x as isize, bounds.min_y + y as isize)),
+            None => self.background(),
+        }
+    }
+}
+
+/// A set of points rendered as the smallest rectangle containing them all.
+/// Each cell is `true` if it is one of the points.
+#[derive(Debug, Clone, Default)]
+pub struct Points {
+    points: HashSet<(isize, isize)>,
+    bounds: Option<Bounds>,
+}
+
+impl FromIterator<(isize, isize)> for Points {
+    fn from_iter<I: IntoIterator<Item = (isize, isize)>>(iter: I) -> Self {
+        let points: HashSet<_> = iter.into_iter().collect();
+        let bounds = Bounds::enclosing(points.iter().copied());
+        Points { points, bounds }
+    }
+}
+
+impl Raster for Points {
+    type Cell = bool;
+
+    fn width(&self) -> usize {
+        self.bounds.map_or(0, |bounds| bounds.width())
+    }
+
+    fn height(&self) -> usize {
+        self.bounds.map_or(0, |bounds| bounds.height())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> &bool {
+        match self.bounds {
+            Some(bounds)
+                if self
+                    .points
+                    .contains(&(bounds.min_x + x as isize, bounds.min_y + y as isize)) =>
+            {
+                &true
+            }
+            _ => &false,
+        }
+    }
+}
+
+/// Draw one line of text per row, converting each cell with `f`.
+pub fn to_ascii<R: Raster>(raster: &R, f: impl Fn(&R::Cell) -> char) -> String {
+    let mut out = String::with_capacity((raster.width() + 1) * raster.height());
+    for y in 0..raster.height() {
+        for x in 0..raster.width() {
+            out.push(f(raster.cell(x, y)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Encode as a binary PPM image. Each cell becomes a `scale` by `scale`
+/// square of the color returned by `f`.
+pub fn to_ppm<R: Raster>(raster: &R, scale: usize, f: impl Fn(&R::Cell) -> Rgb) -> Vec<u8> {
+    let (width, height, pixels) = pixels(raster, scale, f);
+    let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for row in pixels {
+        out.extend(row.into_iter().flatten());
+    }
+    out
+}
+
+/// Encode as a PNG image. Each cell becomes a `scale` by `scale` square of
+/// the color returned by `f`. The image data is stored uncompressed.
+pub fn to_png<R: Raster>(raster: &R, scale: usize, f: impl Fn(&R::Cell) -> Rgb) -> Vec<u8> {
+    let (width, height, pixels) = pixels(raster, scale, f);
+    let mut image_data = Vec::with_capacity(height * (width * 3 + 1));
+    for row in pixels {
+        // Each row starts with its filter type, and we never filter.
+        image_data.push(0);
+        image_data.extend(row.into_iter().flatten());
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no
+    // interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut out, b"IHDR", &header);
+    write_png_chunk(&mut out, b"IDAT", &zlib_stored(&image_data));
+    write_png_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+/// The image's width and height in pixels along with its rows of pixels.
+fn pixels<R: Raster>(
+    raster: &R,
+    scale: usize,
+    f: impl Fn(&R::Cell) -> Rgb,
+) -> (usize, usize, Vec<Vec<Rgb>>) {
+    let width = raster.width() * scale;
+    let height = raster.height() * scale;
+    let rows = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| f(raster.cell(x / scale, y / scale)))
+                .collect()
+        })
+        .collect();
+    (width, height, rows)
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        out.push(is_final as u8);
+        out.extend((block.len() as u16).to_le_bytes());
+        out.extend((!(block.len() as u16)).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+    let points: Points = [(-1, 0), (1, 1)].into_iter().collect();
+    assert_eq!(
+        to_ascii(&points, |&p| if p { '#' } else { '.' }),
+        "#..\n..#\n"
+    );
+    let ppm = to_ppm(&points, 2, |&p| if p { WHITE } else { BLACK });
+    assert_eq!(ppm.len(), b"P6\n6 4\n255\n".len() + 6 * 4 * 3);
+}
+
+#[test]
+fn test_png() {
+    // Big enough that the image data needs more than one stored block.
+    let grid = Grid::from_iter((0..100 * 100).map(|idx| idx % 7 == 0), 100);
+    let color = |&lit: &bool| if lit { [0xff, 0x80, 0x00] } else { BLACK };
+    let png = to_png(&grid, 2, color);
+
+    let mut rest = png
+        .strip_prefix(b"\x89PNG\r\n\x1a\n")
+        .expect("PNG signature");
+    let mut chunks = Vec::new();
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let (kind_and_data, crc) = rest[4..].split_at(4 + len);
+        assert_eq!(crc32(kind_and_data).to_be_bytes(), crc[..4], "chunk CRC");
+        chunks.push((&kind_and_data[..4], &kind_and_data[4..]));
+        rest = &crc[4..];
+    }
+    let kinds: Vec<_> = chunks.iter().map(|&(kind, _)| kind).collect();
+    assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+
+    let header = chunks[0].1;
+    assert_eq!(header[..4], 200u32.to_be_bytes());
+    assert_eq!(header[4..8], 200u32.to_be_bytes());
+    assert_eq!(header[8..], [8, 2, 0, 0, 0]);
+
+    // Inflate the stored deflate blocks.
+    let zlib = chunks[1].1;
+    assert_eq!(u16::from_be_bytes([zlib[0], zlib[1]]) % 31, 0);
+    let mut rest = &zlib[2..];
+    let mut image_data = Vec::new();
+    let mut num_blocks = 0;
+    loop {
+        let header = rest[0];
+        let len = u16::from_le_bytes([rest[1], rest[2]]);
+        let nlen = u16::from_le_bytes([rest[3], rest[4]]);
+        assert_eq!(header & 0b110, 0, "stored block");
+        assert_eq!(nlen, !len);
+        image_data.extend(&rest[5..5 + len as usize]);
+        rest = &rest[5 + len as usize..];
+        num_blocks += 1;
+        if header & 1 == 1 {
+            break;
+        }
+    }
+    assert!(num_blocks > 1);
+    assert_eq!(rest, adler32(&image_data).to_be_bytes());
+
+    let rows: Vec<_> = image_data.chunks(1 + 200 * 3).collect();
+    assert_eq!(rows.len(), 200);
+    for (y, row) in rows.into_iter().enumerate() {
+        assert_eq!(row[0], 0, "filter type");
+        for (x, pixel) in row[1..].chunks(3).enumerate() {
+            assert_eq!(pixel, color(&grid[(X(x / 2), Y(y / 2))]));
+        }
+    }
+}
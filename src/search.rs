@@ -0,0 +1,300 @@
+//! Shortest path searches over any type implementing [`Graph`].
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable from `node` along with the cost of moving to each.
+    /// Costs must not be negative.
+    fn neighbors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, usize)>;
+
+    /// The nodes that can reach `node` along with the cost of each edge. Only
+    /// used by bidirectional search. The default is only correct for graphs
+    /// where every edge can be traversed in both directions at the same cost.
+    fn predecessors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, usize)> {
+        self.neighbors(node)
+    }
+}
+
+/// The nodes visited along a path, starting with the start node and ending
+/// with the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    /// The total cost of the path.
+    pub cost: usize,
+    /// Each node along with the total cost of reaching it from the start.
+    pub steps: Vec<(N, usize)>,
+}
+
+impl<N> Path<N> {
+    pub fn nodes(&self) -> impl Iterator<Item = &N> + '_ {
+        self.steps.iter().map(|(node, _)| node)
+    }
+}
+
+/// Find the lowest cost of travelling from `start` to any node satisfying
+/// `is_goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<usize> {
+    dijkstra_path(graph, start, is_goal).map(|path| path.cost)
+}
+
+/// Like [`dijkstra`], but returns the path taken rather than only its cost.
+pub fn dijkstra_path<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar_path(graph, start, is_goal, |_| 0)
+}
+
+/// Find the lowest cost of travelling from `start` to any node satisfying
+/// `is_goal`. `heuristic` estimates the remaining cost from a node to the
+/// goal; it must never overestimate or the result may not be the lowest cost.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> usize,
+) -> Option<usize> {
+    astar_path(graph, start, is_goal, heuristic).map(|path| path.cost)
+}
+
+/// Like [`astar`], but returns the path taken rather than only its cost.
+pub fn astar_path<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> usize,
+) -> Option<Path<G::Node>> {
+    let mut candidates = BinaryHeap::new();
+    // The lowest known cost of each node along with the node it was reached
+    // from.
+    let mut costs: HashMap<G::Node, (usize, Option<G::Node>)> = HashMap::new();
+    costs.insert(start.clone(), (0, None));
+    candidates.push(State {
+        priority: heuristic(&start),
+        cost: 0,
+        node: start,
+    });
+
+    while let Some(State { cost, node, .. }) = candidates.pop() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&costs, node));
+        }
+        if cost > costs[&node].0 {
+            continue;
+        }
+        for (next_node, edge_cost) in graph.neighbors(&node) {
+            let next_cost = cost + edge_cost;
+            if costs
+                .get(&next_node)
+                .is_none_or(|&(prev_cost, _)| next_cost < prev_cost)
+            {
+                costs.insert(next_node.clone(), (next_cost, Some(node.clone())));
+                candidates.push(State {
+                    priority: next_cost + heuristic(&next_node),
+                    cost: next_cost,
+                    node: next_node,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Follow the chain of previous nodes back from `goal` to the start.
+fn reconstruct_path<N: Clone + Eq + Hash>(
+    costs: &HashMap<N, (usize, Option<N>)>,
+    goal: N,
+) -> Path<N> {
+    let mut steps = Vec::new();
+    let mut node = Some(goal);
+    while let Some(curr) = node {
+        let (cost, prev) = &costs[&curr];
+        node = prev.clone();
+        steps.push((curr, *cost));
+    }
+    steps.reverse();
+    Path {
+        cost: steps.last().map_or(0, |&(_, cost)| cost),
+        steps,
+    }
+}
+
+/// Find the fewest number of edges between `start` and any node satisfying
+/// `is_goal`. Edge costs are ignored.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<usize> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(start.clone());
+    queue.push_back((start, 0));
+    while let Some((node, num_steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(num_steps);
+        }
+        for (next_node, _) in graph.neighbors(&node) {
+            if seen.insert(next_node.clone()) {
+                queue.push_back((next_node, num_steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Find the lowest cost of travelling from `start` to `goal` by searching
+/// forward from `start` and backward from `goal` at the same time. The
+/// backward search follows [`Graph::predecessors`].
+pub fn bidirectional<G: Graph>(graph: &G, start: G::Node, goal: G::Node) -> Option<usize> {
+    let mut best = (start == goal).then_some(0);
+    let mut forward = Frontier::new(start);
+    let mut backward = Frontier::new(goal);
+
+    while let (Some(forward_min), Some(backward_min)) = (forward.min_cost(), backward.min_cost()) {
+        // Any path not yet found must cost at least as much as the cheapest
+        // unexpanded node in each direction.
+        if best.is_some_and(|best| forward_min + backward_min >= best) {
+            break;
+        }
+        let found = if forward_min <= backward_min {
+            forward.expand(&backward, |node| graph.neighbors(node).collect())
+        } else {
+            backward.expand(&forward, |node| graph.predecessors(node).collect())
+        };
+        if let Some(found) = found {
+            best = Some(best.map_or(found, |best| best.min(found)));
+        }
+    }
+    best
+}
+
+/// One direction of a bidirectional search.
+struct Frontier<N> {
+    candidates: BinaryHeap<State<N>>,
+    costs: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Frontier<N> {
+    fn new(start: N) -> Self {
+        let mut candidates = BinaryHeap::new();
+        let mut costs = HashMap::new();
+        costs.insert(start.clone(), 0);
+        candidates.push(State {
+            priority: 0,
+            cost: 0,
+            node: start,
+        });
+        Frontier { candidates, costs }
+    }
+
+    /// The cost of the cheapest node that has yet to be expanded.
+    fn min_cost(&mut self) -> Option<usize> {
+        while let Some(state) = self.candidates.peek() {
+            if state.cost > self.costs[&state.node] {
+                self.candidates.pop();
+            } else {
+                return Some(state.cost);
+            }
+        }
+        None
+    }
+
+    /// Expand the cheapest node. Returns the cost of the cheapest path found
+    /// that joins with `other`.
+    fn expand(&mut self, other: &Self, edges: impl Fn(&N) -> Vec<(N, usize)>) -> Option<usize> {
+        let State { cost, node, .. } = self.candidates.pop()?;
+        let mut found: Option<usize> = None;
+        for (next_node, edge_cost) in edges(&node) {
+            let next_cost = cost + edge_cost;
+            if next_cost < *self.costs.get(&next_node).unwrap_or(&usize::MAX) {
+                if let Some(other_cost) = other.costs.get(&next_node) {
+                    let total = next_cost + other_cost;
+                    found = Some(found.map_or(total, |found| found.min(total)));
+                }
+                self.costs.insert(next_node.clone(), next_cost);
+                self.candidates.push(State {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next_node,
+                });
+            }
+        }
+        found
+    }
+}
+
+struct State<N> {
+    priority: usize,
+    cost: usize,
+    node: N,
+}
+
+impl<N> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // lowest priority first
+        other.priority.cmp(&self.priority)
+    }
+}
+impl<N> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<N> PartialEq for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+impl<N> Eq for State<N> {}
+
+#[test]
+fn test_searches_agree() {
+    // A 1-D line of positions where moving right costs 1 and moving left
+    // costs 3, with a shortcut from 2 to 7 that costs 2.
+    struct Line;
+    impl Graph for Line {
+        type Node = i32;
+        fn neighbors(&self, &node: &i32) -> impl Iterator<Item = (i32, usize)> {
+            let shortcut = (node == 2).then_some((7, 2));
+            [(node + 1, 1), (node - 1, 3)]
+                .into_iter()
+                .chain(shortcut)
+                .filter(|&(n, _)| (0..10).contains(&n))
+        }
+        fn predecessors(&self, &node: &i32) -> impl Iterator<Item = (i32, usize)> {
+            let shortcut = (node == 7).then_some((2, 2));
+            [(node - 1, 1), (node + 1, 3)]
+                .into_iter()
+                .chain(shortcut)
+                .filter(|&(n, _)| (0..10).contains(&n))
+        }
+    }
+    assert_eq!(dijkstra(&Line, 0, |&n| n == 9), Some(6));
+    let path = dijkstra_path(&Line, 0, |&n| n == 9).unwrap();
+    assert_eq!(
+        path.nodes().copied().collect::<Vec<_>>(),
+        [0, 1, 2, 7, 8, 9]
+    );
+    assert_eq!(path.steps[3], (7, 4));
+    assert_eq!(
+        astar(&Line, 0, |&n| n == 9, |&n| (9 - n) as usize / 3),
+        Some(6)
+    );
+    assert_eq!(bidirectional(&Line, 0, 9), Some(6));
+    assert_eq!(bidirectional(&Line, 9, 0), Some(27));
+    assert_eq!(bidirectional(&Line, 4, 4), Some(0));
+    assert_eq!(bfs(&Line, 0, |&n| n == 9), Some(5));
+    assert_eq!(dijkstra(&Line, 0, |&n| n == 10), None);
+}
@@ -0,0 +1,34 @@
+use crate::{error::Result, parse};
+
+use aoc_runner_derive::aoc;
+
+fn parse_depths(input: &str) -> Result<Vec<u64>> {
+    input
+        .split('\n')
+        .map(|line| parse::parse(input, line))
+        .collect()
+}
+
+#[aoc(day1, part1)]
+pub fn part1(input: &str) -> Result<u64> {
+    let entries = parse_depths(input)?;
+    Ok(entries.windows(2).fold(0, |total, items| {
+        let prev = items[0];
+        let curr = items[1];
+        total + ((curr > prev) as u64)
+    }))
+}
+
+#[aoc(day1, part2)]
+pub fn part2(input: &str) -> Result<u64> {
+    let entries = parse_depths(input)?;
+    let entries: Vec<u64> = entries
+        .windows(3)
+        .map(|entries| entries.iter().copied().sum())
+        .collect();
+    Ok(entries.windows(2).fold(0, |total, items| {
+        let prev = items[0];
+        let curr = items[1];
+        total + ((curr > prev) as u64)
+    }))
+}
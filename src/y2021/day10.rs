@@ -0,0 +1,84 @@
+use crate::error::{AocError, Result};
+
+use aoc_runner_derive::aoc;
+
+#[aoc(day10, part1)]
+pub fn part1(input: &str) -> Result<u64> {
+    input
+        .split('\n')
+        .map(|line| {
+            let mut stack = Vec::new();
+            for (idx, b) in line.bytes().enumerate() {
+                match b {
+                    b'(' | b'[' | b'{' | b'<' => {
+                        stack.push(b);
+                    }
+                    b')' => {
+                        if stack.pop() != Some(b'(') {
+                            return Ok(3);
+                        }
+                    }
+                    b']' => {
+                        if stack.pop() != Some(b'[') {
+                            return Ok(57);
+                        }
+                    }
+                    b'}' => {
+                        if stack.pop() != Some(b'{') {
+                            return Ok(1197);
+                        }
+                    }
+                    b'>' => {
+                        if stack.pop() != Some(b'<') {
+                            return Ok(25137);
+                        }
+                    }
+                    _ => return Err(invalid_char(input, line, idx)),
+                }
+            }
+            Ok(0)
+        })
+        .sum()
+}
+
+#[aoc(day10, part2)]
+pub fn part2(input: &str) -> Result<u64> {
+    let mut scores = Vec::new();
+    'lines: for line in input.split('\n') {
+        let mut stack = Vec::new();
+        for (idx, b) in line.bytes().enumerate() {
+            match b {
+                b'(' => stack.push(b')'),
+                b'[' => stack.push(b']'),
+                b'{' => stack.push(b'}'),
+                b'<' => stack.push(b'>'),
+                b')' | b']' | b'}' | b'>' => {
+                    if stack.pop() != Some(b) {
+                        continue 'lines;
+                    }
+                }
+                _ => return Err(invalid_char(input, line, idx)),
+            }
+        }
+        stack.reverse();
+        scores.push(stack.into_iter().fold(0, |total, c| {
+            total * 5
+                + match c {
+                    b')' => 1,
+                    b']' => 2,
+                    b'}' => 3,
+                    b'>' => 4,
+                    _ => unreachable!("only closing characters are pushed"),
+                }
+        }));
+    }
+    if scores.is_empty() {
+        return Err(AocError::new("no incomplete lines"));
+    }
+    scores.sort_unstable();
+    Ok(scores[scores.len() / 2])
+}
+
+fn invalid_char(input: &str, line: &str, idx: usize) -> AocError {
+    AocError::at(input, &line[idx..], "expected one of '()[]{}<>'")
+}
@@ -1,32 +1,25 @@
 use std::cell::Cell;
 
-use crate::grid::{Grid, X, Y};
+use crate::{
+    error::Result,
+    grid::{Grid, X, Y},
+};
 
 use aoc_runner_derive::aoc;
 
 #[aoc(day11, part1)]
-pub fn part1(input: &str) -> usize {
-    let row_len = input.find('\n').unwrap();
-    let data = input
-        .bytes()
-        .filter(|&n| n != b'\n')
-        .map(|b| Cell::new(b - b'0'));
-    let grid = Grid::from_iter(data, row_len);
+pub fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::parse_digits(input)?.map(Cell::new);
     let mut flashes = 0;
     for _ in 0..100 {
         flashes += step(&grid);
     }
-    flashes
+    Ok(flashes)
 }
 
 #[aoc(day11, part2)]
-pub fn part2(input: &str) -> usize {
-    let row_len = input.find('\n').unwrap();
-    let data = input
-        .bytes()
-        .filter(|&n| n != b'\n')
-        .map(|b| Cell::new(b - b'0'));
-    let grid = Grid::from_iter(data, row_len);
+pub fn part2(input: &str) -> Result<usize> {
+    let grid = Grid::parse_digits(input)?.map(Cell::new);
     for step_num in 1.. {
         step(&grid);
         if grid
@@ -35,7 +28,7 @@ pub fn part2(input: &str) -> usize {
             .count()
             == 0
         {
-            return step_num;
+            return Ok(step_num);
         }
     }
     unreachable!();
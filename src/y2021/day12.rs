@@ -1,19 +1,38 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 
 fn is_small_cave(cave: &str) -> bool {
     cave.as_bytes()[0] > b'a'
 }
 
-#[aoc(day12, part1)]
-pub fn part1(input: &str) -> usize {
+fn parse_caves(input: &str) -> Result<HashMap<&str, Vec<&str>>> {
     let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (start, end) in input.split('\n').map(|line| line.split_once('-').unwrap()) {
+    for line in input.split('\n') {
+        let (start, end) = parse::split_once(input, line, "-")?;
+        for cave in [start, end] {
+            if cave.is_empty() || !cave.bytes().all(|b| b.is_ascii_alphabetic()) {
+                return Err(AocError::at(input, cave, "expected a cave name"));
+            }
+        }
         neighbors.entry(start).or_default().push(end);
         neighbors.entry(end).or_default().push(start);
     }
-    count_all_paths(&neighbors)
+    if !neighbors.contains_key("start") {
+        return Err(AocError::new("no start cave"));
+    }
+    Ok(neighbors)
+}
+
+#[aoc(day12, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    let neighbors = parse_caves(input)?;
+    Ok(count_all_paths(&neighbors))
 }
 
 fn count_all_paths(neighbors: &HashMap<&str, Vec<&str>>) -> usize {
@@ -44,13 +63,9 @@ fn traverse<'a, 'b>(
 }
 
 #[aoc(day12, part2)]
-pub fn part2(input: &str) -> usize {
-    let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (start, end) in input.split('\n').map(|line| line.split_once('-').unwrap()) {
-        neighbors.entry(start).or_default().push(end);
-        neighbors.entry(end).or_default().push(start);
-    }
-    count_all_paths2(&neighbors)
+pub fn part2(input: &str) -> Result<usize> {
+    let neighbors = parse_caves(input)?;
+    Ok(count_all_paths2(&neighbors))
 }
 
 fn count_all_paths2(neighbors: &HashMap<&str, Vec<&str>>) -> usize {
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{AocError, Result},
+    grid::SparseGrid,
+    ocr, parse,
+};
+
+use aoc_runner_derive::aoc;
+
+type Dot = (usize, usize);
+
+#[derive(Debug, Clone, Copy)]
+enum Fold {
+    X(usize),
+    Y(usize),
+}
+impl Fold {
+    fn transform(self, (x, y): Dot) -> Dot {
+        match self {
+            Fold::X(fold_value) if x > fold_value => (fold_value - (x - fold_value), y),
+            Fold::Y(fold_value) if y > fold_value => (x, fold_value - (y - fold_value)),
+            _ => (x, y),
+        }
+    }
+}
+
+fn parse_manual(input: &str) -> Result<(Vec<Dot>, Vec<Fold>)> {
+    let (dots, folds) = parse::split_once(input, input, "\n\n")?;
+    let dots = dots
+        .split('\n')
+        .map(|line| {
+            let (x, y) = parse::split_once(input, line, ",")?;
+            Ok((parse::parse(input, x)?, parse::parse(input, y)?))
+        })
+        .collect::<Result<_>>()?;
+    let folds = folds
+        .split('\n')
+        .map(|line| {
+            let fold_spec = parse::strip_prefix(input, line, "fold along ")?;
+            let (x_or_y, fold_value) = parse::split_once(input, fold_spec, "=")?;
+            let fold_value = parse::parse(input, fold_value)?;
+            match x_or_y {
+                "x" => Ok(Fold::X(fold_value)),
+                "y" => Ok(Fold::Y(fold_value)),
+                _ => Err(AocError::at(input, x_or_y, "expected 'x' or 'y'")),
+            }
+        })
+        .collect::<Result<_>>()?;
+    Ok((dots, folds))
+}
+
+#[aoc(day13, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    let (dots, folds) = parse_manual(input)?;
+    let fold = *folds
+        .first()
+        .ok_or_else(|| AocError::new("no fold instructions"))?;
+    let points: HashSet<_> = dots.into_iter().map(|dot| fold.transform(dot)).collect();
+    Ok(points.len())
+}
+
+#[aoc(day13, part2)]
+pub fn part2(input: &str) -> Result<String> {
+    let paper = folded_paper(input)?;
+    ocr::read_letters(paper.iter().map(|((x, y), _)| (x as usize, y as usize)))
+}
+
+/// The paper after every fold, with `true` wherever there is a dot. Render
+/// it with [`crate::render`] to see the letters.
+pub fn folded_paper(input: &str) -> Result<SparseGrid<bool>> {
+    let (dots, folds) = parse_manual(input)?;
+    let mut paper = SparseGrid::new(false);
+    for dot in dots {
+        let (x, y) = folds.iter().fold(dot, |dot, fold| fold.transform(dot));
+        paper.set((x as isize, y as isize), true);
+    }
+    Ok(paper)
+}
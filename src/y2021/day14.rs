@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
+use aoc_runner_derive::aoc;
+
+#[aoc(day14, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    solve(input, 10)
+}
+
+#[aoc(day14, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    solve(input, 40)
+}
+
+fn parse_rules(input: &str, rules: &str) -> Result<HashMap<[u8; 2], u8>> {
+    rules
+        .split('\n')
+        .map(|line| {
+            let (pair, mid) = parse::split_once(input, line, " -> ")?;
+            match (pair.as_bytes(), mid.as_bytes()) {
+                (&[a, b], &[mid]) => Ok(([a, b], mid)),
+                _ => Err(AocError::at(
+                    input,
+                    line,
+                    "expected a rule like \"AB -> C\"",
+                )),
+            }
+        })
+        .collect()
+}
+
+fn solve(input: &str, num_steps: usize) -> Result<usize> {
+    let (start, rules) = parse::split_once(input, input, "\n\n")?;
+    let rules = parse_rules(input, rules)?;
+    if start.is_empty() {
+        return Err(AocError::at(input, start, "expected a polymer template"));
+    }
+
+    let last_letter = start.as_bytes()[start.len() - 1];
+    let pairs = start
+        .as_bytes()
+        .windows(2)
+        .fold(HashMap::new(), |mut pairs, pair| {
+            *pairs.entry([pair[0], pair[1]]).or_insert(0) += 1;
+            pairs
+        });
+
+    let pairs = (0..num_steps).try_fold(pairs, |pairs, _step_num| step(&rules, pairs))?;
+    let mut letters = pairs
+        .into_iter()
+        .fold(HashMap::new(), |mut letters, (pair, occurrences)| {
+            *letters.entry(pair[0]).or_insert(0) += occurrences;
+            letters
+        });
+    *letters.entry(last_letter).or_insert(0) += 1;
+    let (min, max) = letters
+        .values()
+        .fold((usize::MAX, 0), |(min, max), &v| (min.min(v), max.max(v)));
+    Ok(max - min)
+}
+
+fn step(
+    rules: &HashMap<[u8; 2], u8>,
+    before_pairs: HashMap<[u8; 2], usize>,
+) -> Result<HashMap<[u8; 2], usize>> {
+    let mut after = HashMap::new();
+    for (pair, occurrences) in before_pairs {
+        let mid = *rules.get(&pair).ok_or_else(|| {
+            AocError::new(format!(
+                "no insertion rule for {:?}",
+                String::from_utf8_lossy(&pair)
+            ))
+        })?;
+        *after.entry([pair[0], mid]).or_insert(0) += occurrences;
+        *after.entry([mid, pair[1]]).or_insert(0) += occurrences;
+    }
+    Ok(after)
+}
@@ -0,0 +1,113 @@
+use crate::{
+    error::{AocError, Result},
+    grid::{Grid, X, Y},
+    search::{self, Graph, Path},
+};
+
+use aoc_runner_derive::aoc;
+
+fn parse_grid(input: &str) -> Result<Grid<u8>> {
+    Grid::parse_chars_with(input, |b| match b {
+        b'1'..=b'9' => Ok(b - b'0'),
+        _ => Err(AocError::new("expected a risk level 1-9")),
+    })
+}
+
+#[aoc(day15, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(part1_path(input)?.cost)
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    Ok(part2_path(input)?.cost)
+}
+
+/// The lowest risk path through the cavern for part 1, from the top left
+/// position to the bottom right.
+pub fn part1_path(input: &str) -> Result<Path<(X, Y)>> {
+    let grid = parse_grid(input)?;
+    Ok(find_lowest_risk_path(&grid))
+}
+
+/// The lowest risk path through the full cavern for part 2, from the top left
+/// position to the bottom right.
+pub fn part2_path(input: &str) -> Result<Path<(X, Y)>> {
+    let grid = full_cavern(&parse_grid(input)?);
+    Ok(find_lowest_risk_path(&grid))
+}
+
+/// The full cavern, made of 5 by 5 copies of `tile` with risk levels rising
+/// by 1 for each copy right or down, wrapping from 9 back to 1.
+fn full_cavern(tile: &Grid<u8>) -> Grid<u8> {
+    let (tile_width, tile_height) = (tile.width(), tile.height());
+    let grid_data: Vec<u8> = (0..tile_height * 5)
+        .flat_map(|y| (0..tile_width * 5).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let b = tile[(X(x % tile_width), Y(y % tile_height))];
+            let i = (x / tile_width + y / tile_height) as u8;
+            ((b - 1) + i) % 9 + 1
+        })
+        .collect();
+    Grid::new(grid_data, tile_width * 5)
+}
+
+/// The cavern as a graph where the cost of each move is the risk level of the
+/// position being entered.
+struct Cavern<'a>(&'a Grid<u8>);
+
+impl Graph for Cavern<'_> {
+    type Node = (X, Y);
+
+    fn neighbors(&self, &(x, y): &(X, Y)) -> impl Iterator<Item = ((X, Y), usize)> {
+        self.0
+            .neighbors_4(x, y)
+            .map(|pos| (pos, self.0[pos] as usize))
+    }
+}
+
+fn find_lowest_risk_path(grid: &Grid<u8>) -> Path<(X, Y)> {
+    let origin = (X(0), Y(0));
+    let finish = (X(grid.width() - 1), Y(grid.height() - 1));
+    // Every position has a risk level of at least 1 so the manhattan distance
+    // never overestimates the remaining cost.
+    let heuristic = |&(x, y): &(X, Y)| finish.0 .0.abs_diff(x.0) + finish.1 .0.abs_diff(y.0);
+    search::astar_path(&Cavern(grid), origin, |&pos| pos == finish, heuristic)
+        .expect("no path exists")
+}
+
+#[test]
+fn test_paths() {
+    let example = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+    let tile = parse_grid(example).unwrap();
+    for (path, grid, risk) in [
+        (part1_path(example).unwrap(), tile.clone(), 40),
+        (part2_path(example).unwrap(), full_cavern(&tile), 315),
+    ] {
+        assert_eq!(path.cost, risk);
+        let nodes: Vec<_> = path.nodes().copied().collect();
+        assert_eq!(nodes.first(), Some(&(X(0), Y(0))));
+        assert_eq!(
+            nodes.last(),
+            Some(&(X(grid.width() - 1), Y(grid.height() - 1)))
+        );
+        for pair in nodes.windows(2) {
+            let [(x0, y0), (x1, y1)] = pair else {
+                unreachable!("windows of 2");
+            };
+            assert_eq!(x0.0.abs_diff(x1.0) + y0.0.abs_diff(y1.0), 1);
+        }
+        // The start position's risk isn't counted.
+        let total: usize = nodes[1..].iter().map(|&pos| grid[pos] as usize).sum();
+        assert_eq!(total, risk);
+    }
+}
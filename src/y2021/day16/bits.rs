@@ -0,0 +1,275 @@
+//! Read a transmission bit by bit from any source.
+
+use std::{
+    error, fmt,
+    io::{self, Read},
+};
+
+/// How the bytes of a transmission encode its bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Each byte is an ASCII hex digit holding 4 bits. Trailing whitespace
+    /// is ignored.
+    Hex,
+    /// Each byte holds 8 bits.
+    Raw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// The transmission ended in the middle of a packet.
+    Truncated,
+    /// A byte of a hex transmission was not a hex digit.
+    BadHexDigit(u8),
+    /// A literal's value does not fit in 64 bits.
+    LiteralOverflow,
+    /// An operator's sub-packets did not add up to the bit length it gave.
+    LengthMismatch { expected: usize, actual: usize },
+    /// Bits other than zero padding followed the packet.
+    TrailingGarbage,
+    /// Reading from the underlying source failed.
+    Io(io::ErrorKind),
+    /// More bits were asked for at once than fit in a `u64`.
+    TooManyBits(u32),
+}
+
+/// An error decoding a transmission, along with the offset in bits from the
+/// start of the transmission at which it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub kind: DecodeErrorKind,
+    pub bit_offset: usize,
+}
+
+impl DecodeError {
+    pub fn new(kind: DecodeErrorKind, bit_offset: usize) -> Self {
+        DecodeError { kind, bit_offset }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit {}: ", self.bit_offset)?;
+        match &self.kind {
+            DecodeErrorKind::Truncated => write!(f, "transmission truncated"),
+            DecodeErrorKind::BadHexDigit(b) => {
+                write!(f, "expected a hex digit, found {:?}", *b as char)
+            }
+            DecodeErrorKind::LiteralOverflow => write!(f, "literal greater than 64 bits"),
+            DecodeErrorKind::LengthMismatch { expected, actual } => write!(
+                f,
+                "sub-packets were {actual} bits long, expected {expected}"
+            ),
+            DecodeErrorKind::TrailingGarbage => write!(f, "unexpected data after packet"),
+            DecodeErrorKind::Io(kind) => write!(f, "read failed: {kind}"),
+            DecodeErrorKind::TooManyBits(nbits) => {
+                write!(f, "cannot read {nbits} bits at once, at most 64 fit")
+            }
+        }
+    }
+}
+
+impl error::Error for DecodeError {}
+
+/// Reads bits from left to right, most significant bit of each byte or hex
+/// digit first. Bytes are pulled from the source only as they are needed.
+#[derive(Debug)]
+pub struct BitReader<R> {
+    source: R,
+    encoding: Encoding,
+    /// Bits read from the source that have not been consumed yet, in the
+    /// low `num_buffered` bits.
+    buffer: u8,
+    num_buffered: u32,
+    offset: usize,
+}
+
+impl<R: Read> BitReader<R> {
+    pub fn new(source: R, encoding: Encoding) -> Self {
+        BitReader {
+            source,
+            encoding,
+            buffer: 0,
+            num_buffered: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn hex(source: R) -> Self {
+        BitReader::new(source, Encoding::Hex)
+    }
+
+    pub fn raw(source: R) -> Self {
+        BitReader::new(source, Encoding::Raw)
+    }
+
+    /// The number of bits consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn read_bit(&mut self) -> Result<bool, DecodeError> {
+        if self.num_buffered == 0 && !self.fill()? {
+            return Err(DecodeError::new(DecodeErrorKind::Truncated, self.offset));
+        }
+        self.num_buffered -= 1;
+        self.offset += 1;
+        Ok(self.buffer >> self.num_buffered & 1 == 1)
+    }
+
+    /// Read the next `nbits` bits as an unsigned integer. Asking for more
+    /// than 64 bits is an error, and consumes nothing; use
+    /// [`BitReader::read_wide`] for wider fields.
+    pub fn read_bits(&mut self, nbits: u32) -> Result<u64, DecodeError> {
+        if nbits > u64::BITS {
+            return Err(DecodeError::new(
+                DecodeErrorKind::TooManyBits(nbits),
+                self.offset,
+            ));
+        }
+        let mut value = 0;
+        for _ in 0..nbits {
+            value = value << 1 | self.read_bit()? as u64;
+        }
+        Ok(value)
+    }
+
+    /// Read the next `nbits` bits, however many there are, as a big-endian
+    /// unsigned integer. The bits are right-aligned, so the first byte holds
+    /// zero padding when `nbits` is not a multiple of 8.
+    pub fn read_wide(&mut self, nbits: usize) -> Result<Vec<u8>, DecodeError> {
+        let mut bytes = vec![0; nbits.div_ceil(8)];
+        let padding = bytes.len() * 8 - nbits;
+        for bit in padding..bytes.len() * 8 {
+            if self.read_bit()? {
+                bytes[bit / 8] |= 0x80 >> (bit % 8);
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Check that nothing but zero bits remain in the transmission.
+    pub fn finish(mut self) -> Result<(), DecodeError> {
+        loop {
+            if self.num_buffered == 0 && !self.fill()? {
+                return Ok(());
+            }
+            let offset = self.offset;
+            if self.read_bit()? {
+                return Err(DecodeError::new(DecodeErrorKind::TrailingGarbage, offset));
+            }
+        }
+    }
+
+    /// Refill the empty buffer from the source. Returns false at the end of
+    /// the transmission.
+    fn fill(&mut self) -> Result<bool, DecodeError> {
+        let Some(b) = self.next_byte()? else {
+            return Ok(false);
+        };
+        match self.encoding {
+            Encoding::Raw => {
+                self.buffer = b;
+                self.num_buffered = 8;
+            }
+            Encoding::Hex => {
+                if b.is_ascii_whitespace() && self.rest_is_whitespace()? {
+                    return Ok(false);
+                }
+                let digit = (b as char).to_digit(16).ok_or_else(|| {
+                    DecodeError::new(DecodeErrorKind::BadHexDigit(b), self.offset)
+                })?;
+                self.buffer = digit as u8;
+                self.num_buffered = 4;
+            }
+        }
+        Ok(true)
+    }
+
+    fn rest_is_whitespace(&mut self) -> Result<bool, DecodeError> {
+        while let Some(b) = self.next_byte()? {
+            if !b.is_ascii_whitespace() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, DecodeError> {
+        let mut byte = [0];
+        loop {
+            match self.source.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(byte[0])),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    return Err(DecodeError::new(
+                        DecodeErrorKind::Io(err.kind()),
+                        self.offset,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_errors() {
+    use super::Packet;
+
+    let decode = |hex: &str| Packet::decode(BitReader::hex(hex.as_bytes())).map(|_| ());
+    let error = |kind, bit_offset| Err(DecodeError::new(kind, bit_offset));
+    assert_eq!(decode("D2FE28\n"), Ok(()));
+    assert_eq!(decode("D2FE"), error(DecodeErrorKind::Truncated, 16));
+    assert_eq!(
+        decode("D2XE28"),
+        error(DecodeErrorKind::BadHexDigit(b'X'), 8)
+    );
+    assert_eq!(
+        decode("D2FE28 1"),
+        error(DecodeErrorKind::BadHexDigit(b' '), 24)
+    );
+    assert_eq!(
+        decode("D2FE2801"),
+        error(DecodeErrorKind::TrailingGarbage, 31)
+    );
+    // A literal with 17 groups of 1111.
+    assert_eq!(
+        decode("33FFFFFFFFFFFFFFFFFFFDE0"),
+        error(DecodeErrorKind::LiteralOverflow, 86)
+    );
+    // An operator claiming 26 bits of sub-packets holding 27.
+    assert_eq!(
+        decode("38006B45291200"),
+        error(
+            DecodeErrorKind::LengthMismatch {
+                expected: 26,
+                actual: 27
+            },
+            22
+        )
+    );
+
+    let raw = [0xd2, 0xfe, 0x28];
+    assert!(Packet::decode(BitReader::raw(&raw[..])).is_ok());
+
+    let mut reader = BitReader::hex(&b"0123456789ABCDEF0"[..]);
+    assert_eq!(reader.read_bits(4), Ok(0));
+    assert_eq!(
+        reader.read_bits(65),
+        Err(DecodeError::new(DecodeErrorKind::TooManyBits(65), 4))
+    );
+    assert_eq!(reader.read_bits(64), Ok(0x123456789ABCDEF0));
+
+    let mut reader = BitReader::hex(&b"ABCDEF0123456789ABCDEF012"[..]);
+    // 25 hex digits are 100 bits, leaving 4 bits of padding.
+    let wide = vec![
+        0x0a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12,
+    ];
+    assert_eq!(reader.read_wide(100), Ok(wide));
+    assert_eq!(reader.read_wide(0), Ok(vec![]));
+    assert_eq!(
+        reader.read_wide(1),
+        Err(DecodeError::new(DecodeErrorKind::Truncated, 100))
+    );
+}
@@ -0,0 +1,208 @@
+//! Encode packets back into hex transmissions.
+
+use super::{LitOrOp, Op, Packet};
+use crate::error::{AocError, Result};
+
+const LITERAL_TYPE_ID: u64 = 4;
+/// The largest number of sub-packets that fit in an 11 bit count.
+const MAX_SUBPACKET_COUNT: usize = (1 << 11) - 1;
+/// The largest total sub-packet length that fits in a 15 bit length.
+const MAX_SUBPACKET_BITS: usize = (1 << 15) - 1;
+
+impl Packet {
+    /// Encode the packet as a hex transmission that [`Packet::from_hex`]
+    /// decodes back into an identical packet. Like the puzzle's transmissions,
+    /// the output is padded with zero bits to a whole number of bytes.
+    ///
+    /// Operators record their sub-packets by count (length type 1) whenever
+    /// there are few enough of them, and by total bit length (length type 0)
+    /// otherwise.
+    pub fn to_hex(&self) -> Result<String> {
+        let mut bits = BitWriter::default();
+        write_packet(&mut bits, self)?;
+        Ok(bits.into_hex())
+    }
+}
+
+impl Op {
+    fn type_id(self) -> u64 {
+        match self {
+            Op::Sum => 0,
+            Op::Product => 1,
+            Op::Min => 2,
+            Op::Max => 3,
+            Op::Gt => 5,
+            Op::Lt => 6,
+            Op::Eq => 7,
+        }
+    }
+}
+
+/// What is left to write of a packet tree.
+enum Pending<'a> {
+    Packet(&'a Packet),
+    /// Fill in the 15 bit total length at `field` once every sub-packet of
+    /// an operator has been written after it.
+    Length {
+        field: usize,
+        num_packets: usize,
+    },
+}
+
+/// Write `packet` and everything within it. Sub-packets still to be written
+/// are kept on an explicit stack, so deeply nested packets can't exhaust the
+/// call stack.
+fn write_packet(bits: &mut BitWriter, packet: &Packet) -> Result<()> {
+    let mut pending = vec![Pending::Packet(packet)];
+    while let Some(next) = pending.pop() {
+        let packet = match next {
+            Pending::Packet(packet) => packet,
+            Pending::Length { field, num_packets } => {
+                let len = bits.len() - (field + 15);
+                if len > MAX_SUBPACKET_BITS {
+                    return Err(AocError::new(format!(
+                        "{num_packets} sub-packets totalling {len} bits do not fit in either \
+                         length type"
+                    )));
+                }
+                bits.write_at(field, len as u64, 15);
+                continue;
+            }
+        };
+        if packet.version > 7 {
+            return Err(AocError::new(format!(
+                "version {} does not fit in 3 bits",
+                packet.version
+            )));
+        }
+        bits.write(packet.version.into(), 3);
+        match &packet.lit_or_op {
+            LitOrOp::Literal(lit) => {
+                bits.write(LITERAL_TYPE_ID, 3);
+                write_literal(bits, *lit);
+            }
+            LitOrOp::Op { op, packets } => {
+                bits.write(op.type_id(), 3);
+                if packets.len() <= MAX_SUBPACKET_COUNT {
+                    bits.write(1, 1);
+                    bits.write(packets.len() as u64, 11);
+                } else {
+                    // The length isn't known until the sub-packets are
+                    // written, so leave room for it.
+                    bits.write(0, 1);
+                    pending.push(Pending::Length {
+                        field: bits.len(),
+                        num_packets: packets.len(),
+                    });
+                    bits.write(0, 15);
+                }
+                pending.extend(packets.iter().rev().map(Pending::Packet));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write the literal as 4 bit groups, each preceded by a bit that is set on
+/// every group but the last.
+fn write_literal(bits: &mut BitWriter, lit: u64) {
+    let num_groups = (u64::BITS - lit.leading_zeros()).div_ceil(4).max(1);
+    for group in (0..num_groups).rev() {
+        bits.write((group != 0).into(), 1);
+        bits.write(lit >> (group * 4) & 0xf, 4);
+    }
+}
+
+/// Accumulates bits from left to right.
+#[derive(Debug, Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    /// Write the low `nbits` bits of `value`, most significant first.
+    fn write(&mut self, value: u64, nbits: u32) {
+        self.bits
+            .extend((0..nbits).rev().map(|bit| value >> bit & 1 == 1));
+    }
+
+    /// Overwrite the `nbits` bits starting at `at` with the low bits of
+    /// `value`, most significant first.
+    fn write_at(&mut self, at: usize, value: u64, nbits: u32) {
+        for (idx, bit) in (0..nbits).rev().enumerate() {
+            self.bits[at + idx] = value >> bit & 1 == 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// The bits as hex digits, padded with zero bits to a whole number of
+    /// bytes.
+    fn into_hex(mut self) -> String {
+        self.bits.resize(self.bits.len().next_multiple_of(8), false);
+        self.bits
+            .chunks(4)
+            .map(|nibble| {
+                let value = nibble.iter().fold(0, |value, &bit| value << 1 | bit as u32);
+                char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    // Literals and operators using length type 1 encode exactly as given.
+    for hex in ["D2FE28", "EE00D40C823060"] {
+        let packet = Packet::from_hex(hex).unwrap();
+        assert_eq!(packet.to_hex().unwrap(), hex);
+    }
+    for hex in [
+        "38006F45291200",
+        "8A004A801A8002F478",
+        "C0015000016115A2E0802F182340",
+        "9C0141080250320F1802104A08",
+    ] {
+        let packet = Packet::from_hex(hex).unwrap();
+        assert_eq!(Packet::from_hex(&packet.to_hex().unwrap()).unwrap(), packet);
+    }
+
+    // Too many sub-packets to count in 11 bits falls back to length type 0.
+    let lit = |lit| Packet {
+        version: 1,
+        lit_or_op: LitOrOp::Literal(lit),
+    };
+    let packet = Packet {
+        version: 2,
+        lit_or_op: LitOrOp::Op {
+            op: Op::Sum,
+            packets: (0..2500).map(|n| lit(n % 16)).collect(),
+        },
+    };
+    let hex = packet.to_hex().unwrap();
+    // The length type bit follows the 3 bit version and type ID.
+    assert_eq!(u8::from_str_radix(&hex[..2], 16).unwrap() & 0b10, 0);
+    assert_eq!(Packet::from_hex(&hex).unwrap(), packet);
+
+    // Nested deeper than recursive encoding could manage, with the length of
+    // the innermost operator's sub-packets filled in afterwards.
+    let mut deep = packet;
+    for _ in 0..200_000 {
+        deep = Packet {
+            version: 3,
+            lit_or_op: LitOrOp::Op {
+                op: Op::Product,
+                packets: vec![deep].into(),
+            },
+        };
+    }
+    let hex = deep.to_hex().unwrap();
+    assert_eq!(Packet::from_hex(&hex).unwrap().to_hex().unwrap(), hex);
+
+    assert_eq!(
+        Packet::from_hex(&lit(u64::MAX).to_hex().unwrap()).unwrap(),
+        lit(u64::MAX)
+    );
+}
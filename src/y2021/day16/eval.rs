@@ -0,0 +1,156 @@
+//! Evaluate packets as expressions without overflowing.
+
+use super::{visit::PacketFold, Op, Packet};
+
+use std::{error, fmt, marker::PhantomData};
+
+/// An unsigned integer type that packets can be evaluated in.
+pub trait Value: Copy + Ord {
+    fn from_literal(lit: u64) -> Self;
+    fn from_bool(b: bool) -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+impl Value for u64 {
+    fn from_literal(lit: u64) -> Self {
+        lit
+    }
+
+    fn from_bool(b: bool) -> Self {
+        b.into()
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u64::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u64::checked_mul(self, other)
+    }
+}
+
+impl Value for u128 {
+    fn from_literal(lit: u64) -> Self {
+        lit.into()
+    }
+
+    fn from_bool(b: bool) -> Self {
+        b.into()
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u128::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u128::checked_mul(self, other)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// The result of a sum or product does not fit in the value type.
+    Overflow(Op),
+    /// A minimum or maximum of no values.
+    NoOperands(Op),
+    /// A comparison with other than two operands.
+    Arity { op: Op, found: usize },
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::Overflow(op) => write!(f, "{op} overflowed"),
+            EvalError::NoOperands(op) => write!(f, "{op} of no operands"),
+            EvalError::Arity { op, found } => {
+                write!(f, "{op} expects 2 operands, found {found}")
+            }
+        }
+    }
+}
+
+impl error::Error for EvalError {}
+
+impl Packet {
+    /// Evaluate the packet's expression in the integer type `V`. An empty
+    /// sum is 0 and an empty product is 1.
+    pub fn eval<V: Value>(&self) -> Result<V, EvalError> {
+        self.fold(&mut Evaluate(PhantomData))
+    }
+}
+
+struct Evaluate<V>(PhantomData<V>);
+
+impl<V: Value> PacketFold for Evaluate<V> {
+    type Output = Result<V, EvalError>;
+
+    fn literal(&mut self, _packet: &Packet, lit: u64, _depth: usize) -> Self::Output {
+        Ok(V::from_literal(lit))
+    }
+
+    fn op(
+        &mut self,
+        _packet: &Packet,
+        op: Op,
+        operands: Vec<Self::Output>,
+        _depth: usize,
+    ) -> Self::Output {
+        let values = operands.into_iter().collect::<Result<Vec<V>, _>>()?;
+        apply(op, &values)
+    }
+}
+
+/// Apply `op` to its evaluated operands.
+pub(super) fn apply<V: Value>(op: Op, values: &[V]) -> Result<V, EvalError> {
+    let mut iter = values.iter().copied();
+    match op {
+        Op::Sum => iter.try_fold(V::from_literal(0), |acc, value| {
+            acc.checked_add(value).ok_or(EvalError::Overflow(op))
+        }),
+        Op::Product => iter.try_fold(V::from_literal(1), |acc, value| {
+            acc.checked_mul(value).ok_or(EvalError::Overflow(op))
+        }),
+        Op::Min => iter.min().ok_or(EvalError::NoOperands(op)),
+        Op::Max => iter.max().ok_or(EvalError::NoOperands(op)),
+        Op::Gt | Op::Lt | Op::Eq => {
+            let &[a, b] = values else {
+                return Err(EvalError::Arity {
+                    op,
+                    found: values.len(),
+                });
+            };
+            Ok(V::from_bool(match op {
+                Op::Gt => a > b,
+                Op::Lt => a < b,
+                _ => a == b,
+            }))
+        }
+    }
+}
+
+#[test]
+fn test_eval() {
+    let eval = |text: &str| text.parse::<Packet>().unwrap().eval::<u64>();
+    assert_eq!(
+        eval("sum(lit 1, product(lit 2, lit 3), max(lit 4, lit 9))"),
+        Ok(16)
+    );
+    assert_eq!(eval("lt(lit 1, lit 2)"), Ok(1));
+    assert_eq!(eval("sum()"), Ok(0));
+    assert_eq!(
+        eval("sum(lit 18446744073709551615, lit 1)"),
+        Err(EvalError::Overflow(Op::Sum))
+    );
+    assert_eq!(eval("min()"), Err(EvalError::NoOperands(Op::Min)));
+    assert_eq!(
+        eval("eq(lit 1, lit 1, lit 1)"),
+        Err(EvalError::Arity {
+            op: Op::Eq,
+            found: 3
+        })
+    );
+
+    let big: Packet = "product(lit 18446744073709551615, lit 2)".parse().unwrap();
+    assert_eq!(big.eval::<u128>(), Ok(u64::MAX as u128 * 2));
+}
@@ -0,0 +1,369 @@
+use crate::error::{AocError, Result};
+
+use aoc_runner_derive::aoc;
+
+use std::{
+    io::Read,
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+mod bits;
+mod encode;
+mod eval;
+mod syntax;
+mod visit;
+mod vm;
+
+pub use bits::{BitReader, DecodeError, DecodeErrorKind, Encoding};
+pub use eval::{EvalError, Value};
+pub use visit::{PacketFold, PacketVisitor};
+pub use vm::{Instr, Program};
+
+#[aoc(day16, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(sum_version_numbers(&Packet::from_hex(input)?))
+}
+
+fn sum_version_numbers(packet: &Packet) -> usize {
+    let mut sum = 0;
+    packet.walk(&mut |packet: &Packet, _depth| sum += packet.version as usize);
+    sum
+}
+
+#[aoc(day16, part2)]
+pub fn part2(input: &str) -> Result<u64> {
+    Program::compile(&Packet::from_hex(input)?)
+        .run()
+        .map_err(|err| AocError::new(err.to_string()))
+}
+
+/// A packet and everything nested within it. Decoding, encoding, parsing,
+/// printing, walking, cloning, comparing and dropping all work without
+/// recursion, so packets may be nested arbitrarily deep. The exception is
+/// the derived `Debug`, which recurses once per level and so can exhaust the
+/// call stack on very deep packets; use `Display` for those instead.
+#[derive(Debug)]
+pub struct Packet {
+    pub version: u8,
+    pub lit_or_op: LitOrOp,
+}
+
+impl Clone for Packet {
+    fn clone(&self) -> Self {
+        // Like `Packet::read`, keep operators whose sub-packets are still
+        // being cloned on an explicit stack.
+        let mut open: Vec<(&Packet, slice::Iter<Packet>, Vec<Packet>)> = Vec::new();
+        let mut next = self;
+        loop {
+            let mut finished = match &next.lit_or_op {
+                LitOrOp::Literal(lit) => Some(Packet {
+                    version: next.version,
+                    lit_or_op: LitOrOp::Literal(*lit),
+                }),
+                LitOrOp::Op { packets, .. } => {
+                    open.push((next, packets.iter(), Vec::with_capacity(packets.len())));
+                    None
+                }
+            };
+            loop {
+                if let Some(packet) = finished.take() {
+                    match open.last_mut() {
+                        Some((_, _, clones)) => clones.push(packet),
+                        None => return packet,
+                    }
+                }
+                let (_, packets, _) = open.last_mut().expect("an operator is still open");
+                if let Some(packet) = packets.next() {
+                    next = packet;
+                    break;
+                }
+                let (original, _, clones) = open.pop().expect("an operator is still open");
+                let LitOrOp::Op { op, .. } = original.lit_or_op else {
+                    unreachable!("only operators are opened");
+                };
+                finished = Some(Packet {
+                    version: original.version,
+                    lit_or_op: LitOrOp::Op {
+                        op,
+                        packets: clones.into(),
+                    },
+                });
+            }
+        }
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        let mut pending = vec![(self, other)];
+        while let Some((a, b)) = pending.pop() {
+            if a.version != b.version {
+                return false;
+            }
+            match (&a.lit_or_op, &b.lit_or_op) {
+                (LitOrOp::Literal(a), LitOrOp::Literal(b)) if a == b => {}
+                (
+                    LitOrOp::Op { op, packets },
+                    LitOrOp::Op {
+                        op: other_op,
+                        packets: other_packets,
+                    },
+                ) if op == other_op && packets.len() == other_packets.len() => {
+                    pending.extend(packets.iter().zip(other_packets.iter()));
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Packet {}
+
+impl Packet {
+    /// Decode a hex transmission holding a single packet followed by
+    /// nothing but zero padding.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        Packet::decode(BitReader::hex(hex.as_bytes()))
+            .map_err(|err| AocError::at_offset(hex, err.bit_offset / 4, err.to_string()))
+    }
+
+    /// Decode a transmission holding a single packet followed by nothing but
+    /// zero padding.
+    pub fn decode<R: Read>(mut reader: BitReader<R>) -> Result<Self, DecodeError> {
+        let packet = Packet::read(&mut reader)?;
+        reader.finish()?;
+        Ok(packet)
+    }
+
+    /// Read the next packet from `reader`, leaving any bits after it unread.
+    ///
+    /// Operators still waiting on sub-packets are kept on an explicit stack,
+    /// so deeply nested transmissions can't exhaust the call stack.
+    pub fn read<R: Read>(reader: &mut BitReader<R>) -> Result<Self, DecodeError> {
+        let mut open: Vec<OpenOp> = Vec::new();
+        loop {
+            let version = reader.read_bits(3)? as u8;
+            let typ = reader.read_bits(3)?;
+            let op = match typ {
+                0 => Some(Op::Sum),
+                1 => Some(Op::Product),
+                2 => Some(Op::Min),
+                3 => Some(Op::Max),
+                4 => None,
+                5 => Some(Op::Gt),
+                6 => Some(Op::Lt),
+                7 => Some(Op::Eq),
+                _ => unreachable!("packet type is 3 bits"),
+            };
+            let mut finished = match op {
+                None => Some(Packet {
+                    version,
+                    lit_or_op: LitOrOp::Literal(read_literal(reader)?),
+                }),
+                Some(op) => {
+                    open.push(OpenOp::read(reader, version, op)?);
+                    None
+                }
+            };
+
+            // Close every operator whose sub-packets are now all read.
+            loop {
+                if let Some(packet) = finished.take() {
+                    match open.last_mut() {
+                        Some(parent) => parent.packets.push(packet),
+                        None => return Ok(packet),
+                    }
+                }
+                let parent = open.last().expect("an operator is still open");
+                if !parent.is_complete(reader.offset())? {
+                    break;
+                }
+                finished = open.pop().map(OpenOp::into_packet);
+            }
+        }
+    }
+}
+
+fn read_literal<R: Read>(reader: &mut BitReader<R>) -> Result<u64, DecodeError> {
+    let mut lit: u64 = 0;
+    loop {
+        let group_offset = reader.offset();
+        let more = reader.read_bit()?;
+        let group = reader.read_bits(4)?;
+        if lit.leading_zeros() < 4 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::LiteralOverflow,
+                group_offset,
+            ));
+        }
+        lit = (lit << 4) | group;
+        if !more {
+            return Ok(lit);
+        }
+    }
+}
+
+/// An operator packet whose sub-packets are still being read.
+struct OpenOp {
+    version: u8,
+    op: Op,
+    length: SubPacketLength,
+    packets: Vec<Packet>,
+}
+
+enum SubPacketLength {
+    Count(usize),
+    Bits { start: usize, total: usize },
+}
+
+impl OpenOp {
+    /// Read the length type and length that follow an operator's header.
+    fn read<R: Read>(reader: &mut BitReader<R>, version: u8, op: Op) -> Result<Self, DecodeError> {
+        let length_type_id = reader.read_bit()?;
+        let length = if length_type_id {
+            SubPacketLength::Count(reader.read_bits(11)? as usize)
+        } else {
+            let total = reader.read_bits(15)? as usize;
+            SubPacketLength::Bits {
+                start: reader.offset(),
+                total,
+            }
+        };
+        Ok(OpenOp {
+            version,
+            op,
+            length,
+            packets: Vec::new(),
+        })
+    }
+
+    /// Whether every sub-packet has been read, given the reader's `offset`.
+    fn is_complete(&self, offset: usize) -> Result<bool, DecodeError> {
+        match self.length {
+            SubPacketLength::Count(count) => Ok(self.packets.len() == count),
+            SubPacketLength::Bits { start, total } => {
+                let actual = offset - start;
+                if actual > total {
+                    return Err(DecodeError::new(
+                        DecodeErrorKind::LengthMismatch {
+                            expected: total,
+                            actual,
+                        },
+                        start,
+                    ));
+                }
+                Ok(actual == total)
+            }
+        }
+    }
+
+    fn into_packet(self) -> Packet {
+        Packet {
+            version: self.version,
+            lit_or_op: LitOrOp::Op {
+                op: self.op,
+                packets: self.packets.into(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LitOrOp {
+    Literal(u64),
+    Op { op: Op, packets: Packets },
+}
+
+/// The sub-packets of an operator. Derefs to the `Vec` holding them, and
+/// drops them without recursion so deeply nested packets can't exhaust the
+/// call stack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Packets(Vec<Packet>);
+
+impl Packets {
+    pub fn into_vec(mut self) -> Vec<Packet> {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Deref for Packets {
+    type Target = Vec<Packet>;
+
+    fn deref(&self) -> &Vec<Packet> {
+        &self.0
+    }
+}
+
+impl DerefMut for Packets {
+    fn deref_mut(&mut self) -> &mut Vec<Packet> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<Packet>> for Packets {
+    fn from(packets: Vec<Packet>) -> Self {
+        Packets(packets)
+    }
+}
+
+impl FromIterator<Packet> for Packets {
+    fn from_iter<I: IntoIterator<Item = Packet>>(iter: I) -> Self {
+        Packets(iter.into_iter().collect())
+    }
+}
+
+impl Drop for Packets {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.0);
+        while let Some(mut packet) = pending.pop() {
+            if let LitOrOp::Op { packets, .. } = &mut packet.lit_or_op {
+                pending.append(&mut packets.0);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Sum,
+    Product,
+    Min,
+    Max,
+    Gt,
+    Lt,
+    Eq,
+}
+
+#[test]
+fn test_deep_transmission() {
+    // Sums nested deeper than recursive decoding or dropping could manage,
+    // each counting a single sub-packet, around a literal 1. Every packet
+    // has version 1.
+    const DEPTH: usize = 200_000;
+    let mut bits = "001000100000000001".repeat(DEPTH) + "00110000001";
+    let padding = bits.len().next_multiple_of(8) - bits.len();
+    bits += &"0".repeat(padding);
+    let hex: String = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let value = nibble
+                .iter()
+                .fold(0, |value, &bit| value << 1 | (bit - b'0') as u32);
+            char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect();
+
+    assert_eq!(part1(&hex), Ok(DEPTH + 1));
+    assert_eq!(part2(&hex), Ok(1));
+    assert_eq!(Packet::from_hex(&hex).unwrap().to_hex().unwrap(), hex);
+
+    // Packets can still be taken apart, and the pieces dropped, by value.
+    let Packet { lit_or_op, .. } = Packet::from_hex(&hex).unwrap();
+    let LitOrOp::Op { packets, .. } = lit_or_op else {
+        panic!("expected an operator");
+    };
+    assert_eq!(packets.into_vec().len(), 1);
+}
@@ -0,0 +1,261 @@
+//! A text form of packets for reading and writing them by hand.
+//!
+//! A literal is written `lit 5` and an operator as its name followed by its
+//! sub-packets in parentheses, e.g. `gt(lit 1, lit 2)`. Either may be
+//! prefixed by a version such as `v3:`; a missing version means version 0.
+
+use super::{LitOrOp, Op, Packet, PacketVisitor};
+use crate::error::{AocError, Result};
+
+use std::{fmt, str::FromStr};
+
+const OP_NAMES: &[(Op, &str)] = &[
+    (Op::Sum, "sum"),
+    (Op::Product, "product"),
+    (Op::Min, "min"),
+    (Op::Max, "max"),
+    (Op::Gt, "gt"),
+    (Op::Lt, "lt"),
+    (Op::Eq, "eq"),
+];
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name) = OP_NAMES.iter().find(|(op, _)| op == self).unwrap();
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut printer = Printer {
+            f,
+            open: Vec::new(),
+            result: Ok(()),
+        };
+        self.walk(&mut printer);
+        printer.result
+    }
+}
+
+/// Prints a packet through the walk hooks, so deeply nested packets can't
+/// exhaust the call stack.
+struct Printer<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    /// Whether each operator being printed has printed a sub-packet yet.
+    open: Vec<bool>,
+    result: fmt::Result,
+}
+
+impl Printer<'_, '_> {
+    /// Print everything up to the packet's first sub-packet.
+    fn print_start(&mut self, packet: &Packet) -> fmt::Result {
+        if let Some(printed) = self.open.last_mut() {
+            if *printed {
+                self.f.write_str(", ")?;
+            }
+            *printed = true;
+        }
+        if packet.version != 0 {
+            write!(self.f, "v{}:", packet.version)?;
+        }
+        match &packet.lit_or_op {
+            LitOrOp::Literal(lit) => write!(self.f, "lit {lit}"),
+            LitOrOp::Op { op, .. } => {
+                self.open.push(false);
+                write!(self.f, "{op}(")
+            }
+        }
+    }
+}
+
+impl PacketVisitor for Printer<'_, '_> {
+    fn enter(&mut self, packet: &Packet, _depth: usize) {
+        if self.result.is_ok() {
+            self.result = self.print_start(packet);
+        }
+    }
+
+    fn leave(&mut self, packet: &Packet, _depth: usize) {
+        if self.result.is_ok() && matches!(packet.lit_or_op, LitOrOp::Op { .. }) {
+            self.open.pop();
+            self.result = self.f.write_str(")");
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser { input: s, rest: s };
+        let packet = parser.packet()?;
+        parser.skip_whitespace();
+        if !parser.rest.is_empty() {
+            return Err(parser.error("unexpected text after packet"));
+        }
+        Ok(packet)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> AocError {
+        AocError::at_offset(self.input, self.input.len() - self.rest.len(), msg)
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consume `token` if the remaining input starts with it.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{token}'")))
+        }
+    }
+
+    /// Consume a run of characters matching `pred`.
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &str {
+        self.skip_whitespace();
+        let end = self.rest.find(|c| !pred(c)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        taken
+    }
+
+    fn number<T: FromStr>(&mut self, what: &str) -> Result<T> {
+        let start = self.rest.trim_start();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        digits.parse().map_err(|_| {
+            AocError::at_offset(
+                self.input,
+                self.input.len() - start.len(),
+                format!("expected {what}"),
+            )
+        })
+    }
+
+    /// Parse a packet and everything within it. Operators still waiting on
+    /// sub-packets are kept on an explicit stack, so deeply nested text can't
+    /// exhaust the call stack.
+    fn packet(&mut self) -> Result<Packet> {
+        let mut open: Vec<(u8, Op, Vec<Packet>)> = Vec::new();
+        loop {
+            let version = self.version()?;
+            self.skip_whitespace();
+            let name_start = self.rest;
+            let name = self.take_while(|c| c.is_ascii_alphabetic());
+            let mut finished = if name == "lit" {
+                Some(Packet {
+                    version,
+                    lit_or_op: LitOrOp::Literal(self.number("a literal value")?),
+                })
+            } else {
+                let &(op, _) = OP_NAMES
+                    .iter()
+                    .find(|&&(_, op_name)| op_name == name)
+                    .ok_or_else(|| {
+                        AocError::at(self.input, name_start, "expected 'lit' or an operator")
+                    })?;
+                self.expect("(")?;
+                open.push((version, op, Vec::new()));
+                None
+            };
+
+            // Close every operator whose sub-packets are now all parsed.
+            loop {
+                if let Some(packet) = finished.take() {
+                    match open.last_mut() {
+                        Some((_, _, packets)) => packets.push(packet),
+                        None => return Ok(packet),
+                    }
+                }
+                let (_, _, packets) = open.last().expect("an operator is still open");
+                if !self.eat(")") {
+                    if !packets.is_empty() {
+                        self.expect(",")?;
+                    }
+                    break;
+                }
+                let (version, op, packets) = open.pop().expect("an operator is still open");
+                finished = Some(Packet {
+                    version,
+                    lit_or_op: LitOrOp::Op {
+                        op,
+                        packets: packets.into(),
+                    },
+                });
+            }
+        }
+    }
+
+    /// Parse an optional `vN:` version prefix, which defaults to 0.
+    fn version(&mut self) -> Result<u8> {
+        if !self.eat("v") {
+            return Ok(0);
+        }
+        let version = self.number("a version")?;
+        if version > 7 {
+            return Err(self.error("version must be at most 7"));
+        }
+        self.expect(":")?;
+        Ok(version)
+    }
+}
+
+#[test]
+fn test_syntax() {
+    let text = "v3:sum(v1:lit 5, v2:gt(lit 1, lit 2), min())";
+    let packet: Packet = text.parse().unwrap();
+    assert_eq!(packet.to_string(), text);
+    assert_eq!(Packet::from_hex(&packet.to_hex().unwrap()).unwrap(), packet);
+    assert_eq!(
+        " v1 : max ( lit 1 ,lit 2 ) ".parse::<Packet>().unwrap(),
+        "v1:max(lit 1, lit 2)".parse().unwrap()
+    );
+
+    let hex_packet = Packet::from_hex("9C0141080250320F1802104A08").unwrap();
+    assert_eq!(
+        hex_packet.to_string().parse::<Packet>().unwrap(),
+        hex_packet
+    );
+
+    // Nested deeper than recursive parsing, printing, cloning or comparing
+    // could manage.
+    const DEPTH: usize = 200_000;
+    let text = "v1:sum(".repeat(DEPTH) + "lit 1" + &")".repeat(DEPTH);
+    let deep: Packet = text.parse().unwrap();
+    assert_eq!(deep.to_string(), text);
+    assert!(deep.clone() == deep);
+    assert!(text.replace("lit 1", "lit 2").parse::<Packet>().unwrap() != deep);
+
+    for bad in [
+        "v8:lit 1",
+        "lit",
+        "sum(lit 1",
+        "sum(lit 1,)",
+        "sum(,lit 1)",
+        "pow(lit 1)",
+        "lit 1 lit 2",
+    ] {
+        assert!(bad.parse::<Packet>().is_err(), "{bad}");
+    }
+}
@@ -0,0 +1,131 @@
+//! Walk packet trees without writing another recursive `match`.
+//!
+//! Traversal is iterative, so neither walking nor folding can exhaust the
+//! call stack on deeply nested packets.
+
+use super::{LitOrOp, Op, Packet};
+
+/// Hooks called while walking a packet tree depth first. `depth` is 0 for
+/// the packet the walk started from.
+pub trait PacketVisitor {
+    /// Called before any of the packet's sub-packets are visited.
+    fn enter(&mut self, _packet: &Packet, _depth: usize) {}
+
+    /// Called after all of the packet's sub-packets have been visited.
+    fn leave(&mut self, _packet: &Packet, _depth: usize) {}
+}
+
+/// Any closure can be used as a visitor that only enters packets.
+impl<F: FnMut(&Packet, usize)> PacketVisitor for F {
+    fn enter(&mut self, packet: &Packet, depth: usize) {
+        self(packet, depth)
+    }
+}
+
+/// Combine a packet tree into a single value from the bottom up.
+pub trait PacketFold {
+    type Output;
+
+    fn literal(&mut self, packet: &Packet, lit: u64, depth: usize) -> Self::Output;
+
+    /// Combine an operator packet with the results of folding each of its
+    /// sub-packets, in order.
+    fn op(
+        &mut self,
+        packet: &Packet,
+        op: Op,
+        operands: Vec<Self::Output>,
+        depth: usize,
+    ) -> Self::Output;
+}
+
+impl Packet {
+    /// Visit this packet and every packet within it, depth first.
+    pub fn walk(&self, visitor: &mut impl PacketVisitor) {
+        // Each packet is popped twice: first to enter it and queue its
+        // sub-packets, then again to leave it once they are done.
+        let mut pending = vec![(self, 0, false)];
+        while let Some((packet, depth, entered)) = pending.pop() {
+            if entered {
+                visitor.leave(packet, depth);
+                continue;
+            }
+            visitor.enter(packet, depth);
+            pending.push((packet, depth, true));
+            if let LitOrOp::Op { packets, .. } = &packet.lit_or_op {
+                pending.extend(
+                    packets
+                        .iter()
+                        .rev()
+                        .map(|packet| (packet, depth + 1, false)),
+                );
+            }
+        }
+    }
+
+    pub fn fold<F: PacketFold>(&self, fold: &mut F) -> F::Output {
+        let mut folder = Folder {
+            fold,
+            outputs: Vec::new(),
+        };
+        self.walk(&mut folder);
+        folder
+            .outputs
+            .pop()
+            .expect("folding leaves one output for the root")
+    }
+}
+
+/// Adapts a [`PacketFold`] to a [`PacketVisitor`] by keeping the outputs of
+/// sub-packets that have yet to be combined on a stack.
+struct Folder<'a, F: PacketFold> {
+    fold: &'a mut F,
+    outputs: Vec<F::Output>,
+}
+
+impl<F: PacketFold> PacketVisitor for Folder<'_, F> {
+    fn leave(&mut self, packet: &Packet, depth: usize) {
+        let output = match &packet.lit_or_op {
+            LitOrOp::Literal(lit) => self.fold.literal(packet, *lit, depth),
+            LitOrOp::Op { op, packets } => {
+                let operands = self.outputs.split_off(self.outputs.len() - packets.len());
+                self.fold.op(packet, *op, operands, depth)
+            }
+        };
+        self.outputs.push(output);
+    }
+}
+
+#[test]
+fn test_visitors() {
+    use std::collections::HashMap;
+
+    let packet: Packet = "v1:sum(v2:lit 1, v3:max(lit 2, min(lit 3)), v4:max(lit 4))"
+        .parse()
+        .unwrap();
+
+    let mut max_depth = 0;
+    let mut op_counts = HashMap::new();
+    packet.walk(&mut |packet: &Packet, depth| {
+        max_depth = max_depth.max(depth);
+        if let LitOrOp::Op { op, .. } = packet.lit_or_op {
+            *op_counts.entry(op).or_insert(0) += 1;
+        }
+    });
+    assert_eq!(max_depth, 3);
+    assert_eq!(op_counts[&Op::Max], 2);
+    assert_eq!(op_counts.len(), 3);
+
+    // Render the tree bottom up, checking the post hooks see the same order.
+    struct Render;
+    impl PacketFold for Render {
+        type Output = String;
+        fn literal(&mut self, _: &Packet, lit: u64, _: usize) -> String {
+            lit.to_string()
+        }
+        fn op(&mut self, _: &Packet, op: Op, operands: Vec<String>, _: usize) -> String {
+            format!("{op}[{}]", operands.join(" "))
+        }
+    }
+    assert_eq!(packet.fold(&mut Render), "sum[1 max[2 min[3]] max[4]]");
+}
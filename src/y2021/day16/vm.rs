@@ -0,0 +1,152 @@
+//! Compile packets to a flat stack machine program.
+//!
+//! Compiling and running never recurse, so arbitrarily deep packets can be
+//! evaluated without exhausting the call stack, and a compiled program can
+//! be run many times without walking the packet tree again.
+
+use super::{
+    eval::{self, EvalError, Value},
+    visit::PacketVisitor,
+    LitOrOp, Op, Packet,
+};
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instr {
+    /// Push a literal onto the stack.
+    Push(u64),
+    /// Pop the top `arity` values, apply `op` to them in the order they were
+    /// pushed and push the result.
+    Apply { op: Op, arity: usize },
+}
+
+/// A sequence of instructions that leaves a single value on the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    instrs: Vec<Instr>,
+}
+
+impl Program {
+    /// Compile the packet into instructions that push every operand before
+    /// applying its operator.
+    pub fn compile(packet: &Packet) -> Self {
+        let mut compiler = Compiler(Vec::new());
+        packet.walk(&mut compiler);
+        Program { instrs: compiler.0 }
+    }
+
+    pub fn instrs(&self) -> &[Instr] {
+        &self.instrs
+    }
+
+    /// Replace every operator whose operands are all constants with its
+    /// result. Operators that fail to evaluate in `u64` are left alone so
+    /// that [`Program::run`] reports the failure, or succeeds with a wider
+    /// value type.
+    pub fn fold_constants(&self) -> Self {
+        let mut instrs: Vec<Instr> = Vec::with_capacity(self.instrs.len());
+        let mut operands = Vec::new();
+        for &instr in &self.instrs {
+            if let Instr::Apply { op, arity } = instr {
+                let args = instrs.len().saturating_sub(arity);
+                operands.clear();
+                operands.extend(instrs[args..].iter().map_while(|instr| match instr {
+                    Instr::Push(value) => Some(*value),
+                    Instr::Apply { .. } => None,
+                }));
+                if operands.len() == arity {
+                    if let Ok(value) = eval::apply::<u64>(op, &operands) {
+                        instrs.truncate(args);
+                        instrs.push(Instr::Push(value));
+                        continue;
+                    }
+                }
+            }
+            instrs.push(instr);
+        }
+        Program { instrs }
+    }
+
+    /// Run the program, computing in the integer type `V`.
+    pub fn run<V: Value>(&self) -> Result<V, EvalError> {
+        let mut stack = Vec::new();
+        for &instr in &self.instrs {
+            match instr {
+                Instr::Push(lit) => stack.push(V::from_literal(lit)),
+                Instr::Apply { op, arity } => {
+                    let args = stack.len() - arity;
+                    let value = eval::apply(op, &stack[args..])?;
+                    stack.truncate(args);
+                    stack.push(value);
+                }
+            }
+        }
+        assert_eq!(stack.len(), 1, "compiled programs produce one value");
+        Ok(stack[0])
+    }
+}
+
+/// Emits each packet's instruction once all of its operands have been
+/// emitted.
+struct Compiler(Vec<Instr>);
+
+impl PacketVisitor for Compiler {
+    fn leave(&mut self, packet: &Packet, _depth: usize) {
+        self.0.push(match &packet.lit_or_op {
+            LitOrOp::Literal(lit) => Instr::Push(*lit),
+            LitOrOp::Op { op, packets } => Instr::Apply {
+                op: *op,
+                arity: packets.len(),
+            },
+        });
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instr in &self.instrs {
+            match instr {
+                Instr::Push(lit) => writeln!(f, "push {lit}")?,
+                Instr::Apply { op, arity } => writeln!(f, "{op} {arity}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_program() {
+    let packet: Packet = "sum(lit 1, product(lit 2, lit 3), max(lit 4, lit 9), lt(lit 5, lit 6))"
+        .parse()
+        .unwrap();
+    let program = Program::compile(&packet);
+    assert_eq!(program.instrs().len(), 11);
+    assert_eq!(program.run::<u64>(), packet.eval::<u64>());
+    assert_eq!(program.fold_constants().instrs(), [Instr::Push(17)]);
+
+    // Overflowing in u64 is left for run time.
+    let packet: Packet = "sum(product(lit 18446744073709551615, lit 2), lit 1)"
+        .parse()
+        .unwrap();
+    let folded = Program::compile(&packet).fold_constants();
+    assert_eq!(folded.instrs().len(), 5);
+    assert_eq!(folded.run::<u64>(), Err(EvalError::Overflow(Op::Product)));
+    assert_eq!(folded.run::<u128>(), Ok(u64::MAX as u128 * 2 + 1));
+
+    // Deep enough that recursive evaluation would overflow the stack.
+    let mut deep = Packet {
+        version: 0,
+        lit_or_op: LitOrOp::Literal(1),
+    };
+    for _ in 0..1_000_000 {
+        deep = Packet {
+            version: 0,
+            lit_or_op: LitOrOp::Op {
+                op: Op::Sum,
+                packets: vec![deep].into(),
+            },
+        };
+    }
+    assert_eq!(Program::compile(&deep).run::<u64>(), Ok(1));
+}
@@ -1,20 +1,25 @@
 use std::{cmp::Ordering, ops::RangeInclusive};
 
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 
 #[aoc(day17, part1)]
-pub fn part1(input: &str) -> isize {
-    let (_, target_y) = target_ranges(input);
+pub fn part1(input: &str) -> Result<isize> {
+    let (_, target_y) = target_ranges(input)?;
     // The probe goes up and comes down. when it reaches y=0 again the y
     // velocity will be the negated original velocity. The maximum speed it can
     // have at that point is the difference between y=0 and the lowest point of
     // the target range.
-    (target_y.start() + 1) * target_y.start() / 2
+    Ok((target_y.start() + 1) * target_y.start() / 2)
 }
 
 #[aoc(day17, part2)]
-pub fn part2(input: &str) -> usize {
-    let (target_x, target_y) = target_ranges(input);
+pub fn part2(input: &str) -> Result<usize> {
+    let (target_x, target_y) = target_ranges(input)?;
     // find bounds for the x and y velocities. Then simulate each one to see if
     // it is ever within the target area.
 
@@ -37,27 +42,39 @@ pub fn part2(input: &str) -> usize {
 
     // For every (x_vel, y_vel) combination simulate a probe and count the
     // number that fall within the target range.
-    (x_velo_min..=x_velo_max)
+    Ok((x_velo_min..=x_velo_max)
         .flat_map(|x_velo| (y_velo_min..=y_velo_max).map(move |y_velo| (x_velo, y_velo)))
         .filter_map(|(x_velo, y_velo)| {
             probe_iter(x_velo, y_velo)
                 .take_while(|(x, y)| x <= target_x.end() && y >= target_y.start())
                 .find(|(x, y)| target_x.contains(x) && target_y.contains(y))
         })
-        .count()
+        .count())
 }
 
-fn target_ranges(input: &str) -> (RangeInclusive<isize>, RangeInclusive<isize>) {
-    fn str_to_range(input: &str) -> RangeInclusive<isize> {
-        let (start, end) = input.split_once("..").unwrap();
-        start.parse().unwrap()..=end.parse().unwrap()
+fn target_ranges(input: &str) -> Result<(RangeInclusive<isize>, RangeInclusive<isize>)> {
+    fn str_to_range(input: &str, s: &str) -> Result<RangeInclusive<isize>> {
+        let (start, end) = parse::split_once(input, s, "..")?;
+        let range = parse::parse(input, start)?..=parse::parse(input, end)?;
+        if range.is_empty() {
+            return Err(AocError::at(input, s, "empty range"));
+        }
+        Ok(range)
     }
 
-    let input = input.strip_prefix("target area: ").unwrap();
-    let (x, y) = input.split_once(", ").unwrap();
-    let x = x.strip_prefix("x=").unwrap();
-    let y = y.strip_prefix("y=").unwrap();
-    (str_to_range(x), str_to_range(y))
+    let area = parse::strip_prefix(input, input, "target area: ")?;
+    let (x, y) = parse::split_once(input, area, ", ")?;
+    let x = str_to_range(input, parse::strip_prefix(input, x, "x=")?)?;
+    let y = str_to_range(input, parse::strip_prefix(input, y, "y=")?)?;
+    // The solutions assume the target is ahead of and below the launcher.
+    if *x.start() <= 0 || *y.end() >= 0 {
+        return Err(AocError::at(
+            input,
+            area,
+            "target must be at x > 0 and y < 0",
+        ));
+    }
+    Ok((x, y))
 }
 
 fn probe_iter(x_vel: isize, y_vel: isize) -> ProbeIter {
@@ -1,22 +1,23 @@
+use crate::error::{AocError, Result};
+
 use aoc_runner_derive::aoc;
 
 #[aoc(day18, part1)]
-pub fn part1(input: &str) -> usize {
-    let sum = input
-        .lines()
-        .map(parse_pair_or_num)
+pub fn part1(input: &str) -> Result<usize> {
+    let sum = parse_numbers(input)?
+        .into_iter()
         .reduce(|left, right| {
             let mut pair = PairOrNum::Pair(Box::new(left), Box::new(right));
             reduce(&mut pair);
             pair
         })
-        .unwrap();
-    magnitude(&sum)
+        .ok_or_else(|| AocError::new("no numbers"))?;
+    Ok(magnitude(&sum))
 }
 
 #[aoc(day18, part2)]
-pub fn part2(input: &str) -> usize {
-    let numbers: Vec<_> = input.lines().map(parse_pair_or_num).collect();
+pub fn part2(input: &str) -> Result<usize> {
+    let numbers = parse_numbers(input)?;
     numbers
         .iter()
         .flat_map(|a| numbers.iter().map(move |b| (a, b)))
@@ -26,7 +27,14 @@ pub fn part2(input: &str) -> usize {
             magnitude(&pair)
         })
         .max()
-        .unwrap()
+        .ok_or_else(|| AocError::new("no numbers"))
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<PairOrNum>> {
+    input
+        .lines()
+        .map(|line| parse_pair_or_num(input, line))
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -35,19 +43,53 @@ enum PairOrNum {
     Pair(Box<PairOrNum>, Box<PairOrNum>),
 }
 
-fn parse_pair_or_num(input: &str) -> PairOrNum {
-    fn _parse_pair_or_num(i: &[u8]) -> (&[u8], PairOrNum) {
-        if i[0] == b'[' {
-            let (rem, left) = _parse_pair_or_num(&i[1..]);
-            let (rem, right) = _parse_pair_or_num(&rem[1..]);
-            (&rem[1..], PairOrNum::Pair(Box::new(left), Box::new(right)))
-        } else {
-            (&i[1..], PairOrNum::Num((i[0] - b'0') as usize))
+/// Pairs in the puzzle input are never nested more deeply than this, so that
+/// adding two numbers leaves every pair that explodes holding two regular
+/// numbers.
+const MAX_DEPTH: usize = 4;
+
+/// Parse the snailfish number on `line`, a subslice of the full puzzle `input`.
+fn parse_pair_or_num(input: &str, line: &str) -> Result<PairOrNum> {
+    // Report an error at the start of `rem`, the unparsed remainder of `line`.
+    fn err(input: &str, line: &str, rem: &[u8], msg: &str) -> AocError {
+        AocError::at(input, &line[line.len() - rem.len()..], msg)
+    }
+    fn expect<'a>(input: &str, line: &str, rem: &'a [u8], b: u8) -> Result<&'a [u8]> {
+        match rem.split_first() {
+            Some((&first, rem)) if first == b => Ok(rem),
+            _ => Err(err(input, line, rem, &format!("expected {:?}", b as char))),
         }
     }
-    let (rem, pn) = _parse_pair_or_num(input.as_bytes());
-    assert!(rem.is_empty());
-    pn
+    // `depth` is the number of pairs enclosing `i`.
+    fn _parse_pair_or_num<'a>(
+        input: &str,
+        line: &str,
+        i: &'a [u8],
+        depth: usize,
+    ) -> Result<(&'a [u8], PairOrNum)> {
+        match i.first() {
+            Some(b'[') if depth == MAX_DEPTH => Err(err(
+                input,
+                line,
+                i,
+                &format!("pair nested more than {MAX_DEPTH} deep"),
+            )),
+            Some(b'[') => {
+                let (rem, left) = _parse_pair_or_num(input, line, &i[1..], depth + 1)?;
+                let rem = expect(input, line, rem, b',')?;
+                let (rem, right) = _parse_pair_or_num(input, line, rem, depth + 1)?;
+                let rem = expect(input, line, rem, b']')?;
+                Ok((rem, PairOrNum::Pair(Box::new(left), Box::new(right))))
+            }
+            Some(b) if b.is_ascii_digit() => Ok((&i[1..], PairOrNum::Num((b - b'0') as usize))),
+            _ => Err(err(input, line, i, "expected '[' or a digit")),
+        }
+    }
+    let (rem, pn) = _parse_pair_or_num(input, line, line.as_bytes(), 0)?;
+    if !rem.is_empty() {
+        return Err(err(input, line, rem, "unexpected trailing characters"));
+    }
+    Ok(pn)
 }
 
 fn reduce(pn: &mut PairOrNum) {
@@ -60,7 +102,7 @@ fn reduce(pn: &mut PairOrNum) {
 fn magnitude(pn: &PairOrNum) -> usize {
     match pn {
         &PairOrNum::Num(x) => x,
-        PairOrNum::Pair(left, right) => 3 * magnitude(&*left) + 2 * magnitude(&*right),
+        PairOrNum::Pair(left, right) => 3 * magnitude(left) + 2 * magnitude(right),
     }
 }
 
@@ -123,7 +165,7 @@ fn split(pn: &mut PairOrNum) -> bool {
             if x >= 10 {
                 *pn = PairOrNum::Pair(
                     Box::new(PairOrNum::Num(x / 2)),
-                    Box::new(PairOrNum::Num((x + 1) / 2)),
+                    Box::new(PairOrNum::Num(x.div_ceil(2))),
                 );
                 true
             } else {
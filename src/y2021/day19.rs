@@ -1,5 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 
 /// Spin the orientation 90 degrees leaving the z-axis alone.
@@ -72,12 +77,12 @@ const ORIENTATIONS: [TransformFn; 24] = [
 ];
 
 #[aoc(day19, part1)]
-pub fn part1(input: &str) -> usize {
-    let mut scanners = parse(input);
+pub fn part1(input: &str) -> Result<usize> {
+    let mut scanners = parse(input)?;
     let mut found = vec![scanners.remove(0)];
 
     while !scanners.is_empty() {
-        let (scanner_idx, rotate_transform, offsets) = find_overlap(&found, &scanners);
+        let (scanner_idx, rotate_transform, offsets) = find_overlap(&found, &scanners)?;
         let mut found_scanner = scanners.remove(scanner_idx);
         let transform = |xyz| {
             let (x, y, z) = rotate_transform(xyz);
@@ -89,17 +94,17 @@ pub fn part1(input: &str) -> usize {
         found.push(found_scanner);
     }
     let unique_beacons: HashSet<_> = found.into_iter().flat_map(|x| x.into_iter()).collect();
-    unique_beacons.len()
+    Ok(unique_beacons.len())
 }
 
 #[aoc(day19, part2)]
-pub fn part2(input: &str) -> isize {
-    let mut scanners = parse(input);
+pub fn part2(input: &str) -> Result<isize> {
+    let mut scanners = parse(input)?;
     let mut found = vec![scanners.remove(0)];
     let mut found_offsets = vec![(0, 0, 0)];
 
     while !scanners.is_empty() {
-        let (scanner_idx, rotate_transform, offsets) = find_overlap(&found, &scanners);
+        let (scanner_idx, rotate_transform, offsets) = find_overlap(&found, &scanners)?;
         let mut found_scanner = scanners.remove(scanner_idx);
         let transform = |xyz| {
             let (x, y, z) = rotate_transform(xyz);
@@ -111,7 +116,7 @@ pub fn part2(input: &str) -> isize {
         found.push(found_scanner);
         found_offsets.push(offsets);
     }
-    found_offsets
+    Ok(found_offsets
         .iter()
         .flat_map(|offsets_a| {
             found_offsets
@@ -124,21 +129,24 @@ pub fn part2(input: &str) -> isize {
                 + (offsets_a.2 - offsets_b.2).abs()
         })
         .max()
-        .unwrap()
+        .unwrap())
 }
 
 fn find_overlap(
     already_found: &[Vec<(isize, isize, isize)>],
     looking: &[Vec<(isize, isize, isize)>],
-) -> (usize, TransformFn, (isize, isize, isize)) {
+) -> Result<(usize, TransformFn, (isize, isize, isize))> {
     for found in already_found {
         for (idx, look) in looking.iter().enumerate() {
             if let Some((transform, offsets)) = is_overlapping(found, look) {
-                return (idx, transform, offsets);
+                return Ok((idx, transform, offsets));
             }
         }
     }
-    panic!("no overlap found");
+    Err(AocError::new(format!(
+        "{} scanners don't overlap any other scanner",
+        looking.len()
+    )))
 }
 
 fn is_overlapping(
@@ -169,20 +177,23 @@ fn is_overlapping(
     None
 }
 
-fn parse(input: &str) -> Vec<Vec<(isize, isize, isize)>> {
+fn parse(input: &str) -> Result<Vec<Vec<(isize, isize, isize)>>> {
     input
         .split("\n\n")
         .map(|scanner_input| {
-            scanner_input
-                .lines()
-                .skip(1)
+            let mut lines = scanner_input.lines();
+            let header = parse::next(input, &mut lines, scanner_input, "scanner header")?;
+            parse::strip_prefix(input, header, "--- scanner ")?;
+            lines
                 .map(|line| {
-                    let mut iter = line.split(',').map(|x| x.parse().unwrap());
-                    (
-                        iter.next().unwrap(),
-                        iter.next().unwrap(),
-                        iter.next().unwrap(),
-                    )
+                    let mut iter = line.split(',');
+                    let mut coord =
+                        || parse::parse(input, parse::next(input, &mut iter, line, "coordinate")?);
+                    let beacon = (coord()?, coord()?, coord()?);
+                    if iter.next().is_some() {
+                        return Err(AocError::at(input, line, "expected 3 coordinates"));
+                    }
+                    Ok(beacon)
                 })
                 .collect()
         })
@@ -0,0 +1,71 @@
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
+use aoc_runner_derive::aoc;
+
+struct XY {
+    x: u64,
+    y: u64,
+}
+
+enum Dir {
+    Forward,
+    Up,
+    Down,
+}
+
+fn parse_commands(input: &str) -> Result<Vec<(Dir, u64)>> {
+    input
+        .split('\n')
+        .map(|line| {
+            let (dir, mag) = parse::split_once(input, line, " ")?;
+            let dir = match dir {
+                "forward" => Dir::Forward,
+                "up" => Dir::Up,
+                "down" => Dir::Down,
+                _ => return Err(AocError::at(input, dir, "invalid direction")),
+            };
+            Ok((dir, parse::parse(input, mag)?))
+        })
+        .collect()
+}
+
+#[aoc(day2, part1)]
+pub fn part1(input: &str) -> Result<u64> {
+    let pos = parse_commands(input)?
+        .into_iter()
+        .fold(XY { x: 0, y: 0 }, |curr, (dir, mag)| match dir {
+            Dir::Forward => XY {
+                x: curr.x + mag,
+                ..curr
+            },
+            Dir::Up => XY {
+                y: curr.y - mag,
+                ..curr
+            },
+            Dir::Down => XY {
+                y: curr.y + mag,
+                ..curr
+            },
+        });
+    Ok(pos.x * pos.y)
+}
+
+#[aoc(day2, part2)]
+pub fn part2(input: &str) -> Result<u64> {
+    let mut aim = 0;
+    let mut pos = XY { x: 0, y: 0 };
+    for (dir, mag) in parse_commands(input)? {
+        match dir {
+            Dir::Forward => {
+                pos.x += mag;
+                pos.y += aim * mag;
+            }
+            Dir::Up => aim -= mag,
+            Dir::Down => aim += mag,
+        }
+    }
+    Ok(pos.x * pos.y)
+}
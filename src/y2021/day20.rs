@@ -0,0 +1,122 @@
+use crate::{
+    error::{AocError, Result},
+    grid::SparseGrid,
+    parse,
+};
+
+use aoc_runner_derive::aoc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LitOrDark {
+    Lit,
+    Dark,
+}
+impl LitOrDark {
+    fn is_lit(self) -> bool {
+        self == LitOrDark::Lit
+    }
+}
+
+#[derive(Debug, Default)]
+struct EnhancementBits([u64; 8]);
+impl EnhancementBits {
+    fn get_bit(&self, b: u16) -> LitOrDark {
+        assert!(b <= 512);
+        let chunk = b as usize / 64;
+        let bit = b % 64;
+        if self.0[chunk] & (1 << bit) == 0 {
+            LitOrDark::Dark
+        } else {
+            LitOrDark::Lit
+        }
+    }
+
+    fn set_bit(&mut self, b: u16) {
+        assert!(b <= 512);
+        let chunk = b as usize / 64;
+        let bit = b % 64;
+        self.0[chunk] |= 1 << bit;
+    }
+}
+
+/// The image is infinite; every pixel outside the stored region has the
+/// grid's background value.
+type Image = SparseGrid<LitOrDark>;
+
+fn enhance(image: &Image, enhancement: &EnhancementBits) -> Image {
+    let background = match image.background() {
+        LitOrDark::Lit => enhancement.get_bit(0x1ff),
+        LitOrDark::Dark => enhancement.get_bit(0),
+    };
+    let mut enhanced = SparseGrid::new(background);
+    let Some(bounds) = image.bounds() else {
+        return enhanced;
+    };
+    for (x, y) in bounds.expand(1).points() {
+        let mut enhance_idx = 0;
+        for (dx, dy) in [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (0, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ] {
+            enhance_idx = enhance_idx << 1 | image.get((x + dx, y + dy)).is_lit() as u16;
+        }
+        enhanced.set((x, y), enhancement.get_bit(enhance_idx));
+    }
+    enhanced
+}
+
+/// Whether the byte at `idx` within `s`, a subslice of `input`, is lit.
+fn parse_pixel(input: &str, s: &str, idx: usize) -> Result<bool> {
+    match s.as_bytes()[idx] {
+        b'#' => Ok(true),
+        b'.' => Ok(false),
+        _ => Err(AocError::at(input, &s[idx..], "expected '#' or '.'")),
+    }
+}
+
+fn solve(input: &str, num_iters: usize) -> Result<usize> {
+    let (enhancement_input, image_input) = parse::split_once(input, input, "\n\n")?;
+    if enhancement_input.len() != 512 {
+        return Err(AocError::at(
+            input,
+            enhancement_input,
+            "enhancement algorithm must be 512 characters",
+        ));
+    }
+    let mut enhancement = EnhancementBits::default();
+    for i in 0..enhancement_input.len() {
+        if parse_pixel(input, enhancement_input, i)? {
+            enhancement.set_bit(i as u16);
+        }
+    }
+    let mut image = Image::new(LitOrDark::Dark);
+    for (y, line) in image_input.split('\n').enumerate() {
+        for x in 0..line.len() {
+            if parse_pixel(input, line, x)? {
+                image.set((x as isize, y as isize), LitOrDark::Lit);
+            }
+        }
+    }
+    let enhanced = (0..num_iters).fold(image, |image, _| enhance(&image, &enhancement));
+    if enhanced.background().is_lit() {
+        return Err(AocError::new("infinitely many pixels are lit"));
+    }
+    Ok(enhanced.len())
+}
+
+#[aoc(day20, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    solve(input, 2)
+}
+
+#[aoc(day20, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    solve(input, 50)
+}
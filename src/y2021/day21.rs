@@ -1,36 +1,38 @@
 use std::{collections::HashMap, iter::Cycle, ops::RangeInclusive};
 
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 
 #[aoc(day21, part1)]
-pub fn part1(input: &str) -> usize {
-    let mut players = parse(input);
+pub fn part1(input: &str) -> Result<usize> {
+    let mut players = parse(input)?;
     for (num_rolls, roll) in dice_iter().enumerate() {
         let player = &mut players[roll % 2];
         player.advance(roll);
         player.score += player.pos;
         if player.score >= 1000 {
-            return players[(roll + 1) % 2].score * ((num_rolls + 1) * 3);
+            return Ok(players[(roll + 1) % 2].score * ((num_rolls + 1) * 3));
         }
     }
     unreachable!();
 }
 
-fn parse(input: &str) -> [Player; 2] {
-    let mut starting_positions = input.split('\n').map(|line| {
-        let (_, start_pos) = line.split_once(": ").unwrap();
-        start_pos.parse().unwrap()
-    });
-    [
-        Player {
-            score: 0,
-            pos: starting_positions.next().unwrap(),
-        },
-        Player {
-            score: 0,
-            pos: starting_positions.next().unwrap(),
-        },
-    ]
+fn parse(input: &str) -> Result<[Player; 2]> {
+    let mut lines = input.split('\n');
+    let mut next_player = || -> Result<Player> {
+        let line = parse::next(input, &mut lines, input, "a starting position")?;
+        let (_, start_pos) = parse::split_once(input, line, ": ")?;
+        let pos = parse::parse(input, start_pos)?;
+        if !(1..=10).contains(&pos) {
+            return Err(AocError::at(input, start_pos, "position must be 1-10"));
+        }
+        Ok(Player { score: 0, pos })
+    };
+    Ok([next_player()?, next_player()?])
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -56,12 +58,12 @@ fn dice_iter() -> impl Iterator<Item = usize> {
 }
 
 #[aoc(day21, part2)]
-pub fn part2(input: &str) -> usize {
-    let players = parse(input);
-    play_quantum(&mut HashMap::new(), players)
+pub fn part2(input: &str) -> Result<usize> {
+    let players = parse(input)?;
+    Ok(play_quantum(&mut HashMap::new(), players)
         .into_iter()
         .max()
-        .unwrap()
+        .unwrap())
 }
 
 fn play_quantum(cache: &mut HashMap<[Player; 2], [usize; 2]>, players: [Player; 2]) -> [usize; 2] {
@@ -0,0 +1,220 @@
+//! Export the lit cubes of a reactor as a 3D surface mesh.
+
+use super::{Cuboid, DisjointReactor};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+};
+
+/// An axis-aligned rectangle on the surface of the lit volume.
+#[derive(Debug, Clone)]
+struct Face {
+    /// The axis the face is perpendicular to.
+    axis: usize,
+    /// The face's position along `axis`.
+    coord: isize,
+    /// Whether the outward normal points along the positive `axis`.
+    positive: bool,
+    /// The extent of the face along the two other axes, in the order
+    /// `axis + 1`, `axis + 2` (wrapping around).
+    rect: Cuboid<2>,
+}
+
+impl Face {
+    /// The corners counterclockwise as seen from outside the volume.
+    fn corners(&self) -> [[isize; 3]; 4] {
+        let (min, max) = (self.rect.min, self.rect.max);
+        let mut corners = [
+            [min[0], min[1]],
+            [max[0], min[1]],
+            [max[0], max[1]],
+            [min[0], max[1]],
+        ];
+        if !self.positive {
+            corners.reverse();
+        }
+        corners.map(|[u, v]| {
+            let mut corner = [0; 3];
+            corner[self.axis] = self.coord;
+            corner[(self.axis + 1) % 3] = u;
+            corner[(self.axis + 2) % 3] = v;
+            corner
+        })
+    }
+
+    fn normal(&self) -> [isize; 3] {
+        let mut normal = [0; 3];
+        normal[self.axis] = if self.positive { 1 } else { -1 };
+        normal
+    }
+}
+
+/// The boundary of a set of disjoint cuboids. Faces shared by two adjacent
+/// cuboids are inside the volume and are left out.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    faces: Vec<Face>,
+}
+
+impl Mesh {
+    /// Build the mesh of `cuboids`, which must not overlap. When `clip` is
+    /// given only the parts of the cuboids within it are included.
+    pub fn from_cuboids<'a>(
+        cuboids: impl IntoIterator<Item = &'a Cuboid<3>>,
+        clip: Option<&Cuboid<3>>,
+    ) -> Self {
+        // The faces in each plane, split by the direction of their normal.
+        let mut planes: BTreeMap<(usize, isize), (Vec<Cuboid<2>>, Vec<Cuboid<2>>)> =
+            BTreeMap::new();
+        for cuboid in cuboids {
+            let cuboid = match clip {
+                Some(clip) => match cuboid.intersection(clip) {
+                    Some(clipped) => clipped,
+                    None => continue,
+                },
+                None => cuboid.clone(),
+            };
+            for axis in 0..3 {
+                let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                let rect = Cuboid::new(
+                    [cuboid.min[u], cuboid.min[v]],
+                    [cuboid.max[u], cuboid.max[v]],
+                );
+                planes
+                    .entry((axis, cuboid.min[axis]))
+                    .or_default()
+                    .0
+                    .push(rect.clone());
+                planes
+                    .entry((axis, cuboid.max[axis]))
+                    .or_default()
+                    .1
+                    .push(rect);
+            }
+        }
+
+        // Where a face of one cuboid meets the opposite face of its
+        // neighbour, both are interior.
+        let mut faces = Vec::new();
+        for ((axis, coord), (negative, positive)) in planes {
+            for (rects, holes, positive) in
+                [(&negative, &positive, false), (&positive, &negative, true)]
+            {
+                faces.extend(subtract_all(rects, holes).into_iter().map(|rect| Face {
+                    axis,
+                    coord,
+                    positive,
+                    rect,
+                }));
+            }
+        }
+        Mesh { faces }
+    }
+
+    /// The number of rectangular faces in the mesh.
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Write the mesh as a Wavefront OBJ file of quads.
+    pub fn write_obj(&self, w: &mut impl Write) -> io::Result<()> {
+        let mut vertices = Vec::new();
+        let mut vertex_ids = HashMap::new();
+        let faces: Vec<_> = self
+            .faces
+            .iter()
+            .map(|face| {
+                face.corners().map(|corner| {
+                    *vertex_ids.entry(corner).or_insert_with(|| {
+                        vertices.push(corner);
+                        vertices.len()
+                    })
+                })
+            })
+            .collect();
+        for [x, y, z] in vertices {
+            writeln!(w, "v {x} {y} {z}")?;
+        }
+        for [a, b, c, d] in faces {
+            writeln!(w, "f {a} {b} {c} {d}")?;
+        }
+        Ok(())
+    }
+
+    /// Write the mesh as an ASCII STL file, with two triangles per face.
+    pub fn write_stl(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "solid reactor")?;
+        for face in &self.faces {
+            let [nx, ny, nz] = face.normal();
+            let [a, b, c, d] = face.corners();
+            for triangle in [[a, b, c], [a, c, d]] {
+                writeln!(w, "  facet normal {nx} {ny} {nz}")?;
+                writeln!(w, "    outer loop")?;
+                for [x, y, z] in triangle {
+                    writeln!(w, "      vertex {x} {y} {z}")?;
+                }
+                writeln!(w, "    endloop")?;
+                writeln!(w, "  endfacet")?;
+            }
+        }
+        writeln!(w, "endsolid reactor")
+    }
+}
+
+impl DisjointReactor<3> {
+    /// The surface of the lit cubes, optionally limited to `clip`.
+    pub fn mesh(&self, clip: Option<&Cuboid<3>>) -> Mesh {
+        Mesh::from_cuboids(self.lit_cuboids(), clip)
+    }
+}
+
+/// The parts of `rects` not covered by any of `holes`.
+fn subtract_all(rects: &[Cuboid<2>], holes: &[Cuboid<2>]) -> Vec<Cuboid<2>> {
+    let mut remaining = rects.to_vec();
+    for hole in holes {
+        remaining = remaining
+            .into_iter()
+            .flat_map(|rect| {
+                if rect.intersection(hole).is_some() {
+                    rect.difference(hole)
+                } else {
+                    vec![rect]
+                }
+            })
+            .collect();
+    }
+    remaining
+}
+
+#[test]
+fn test_mesh() {
+    // Two unit cubes side by side share a face that must not be exported.
+    let cuboids = [
+        Cuboid::new([0, 0, 0], [1, 1, 1]),
+        Cuboid::new([1, 0, 0], [2, 1, 1]),
+    ];
+    let mesh = Mesh::from_cuboids(&cuboids, None);
+    assert_eq!(mesh.num_faces(), 10);
+
+    let mut obj = Vec::new();
+    mesh.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("v ")).count(),
+        12
+    );
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("f ")).count(),
+        10
+    );
+
+    let mut stl = Vec::new();
+    mesh.write_stl(&mut stl).unwrap();
+    let stl = String::from_utf8(stl).unwrap();
+    assert_eq!(stl.matches("facet normal").count(), 20);
+
+    // Clipping to the first cube leaves a closed box of 6 faces.
+    let clip = Cuboid::new([-5, -5, -5], [1, 5, 5]);
+    assert_eq!(Mesh::from_cuboids(&cuboids, Some(&clip)).num_faces(), 6);
+}
@@ -0,0 +1,361 @@
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
+use aoc_runner_derive::aoc;
+
+mod mesh;
+
+pub use mesh::Mesh;
+
+/// The region part 1 is limited to: -50..=50 on every axis.
+const INITIALIZATION_REGION: Cuboid<3> = Cuboid {
+    min: [-50; 3],
+    max: [51; 3],
+};
+
+#[aoc(day22, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    let reactor: DisjointReactor<3> = reboot(parse_steps(input)?);
+    Ok(reactor.count_on_in(&INITIALIZATION_REGION))
+}
+
+#[aoc(day22, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    Ok(solve::<3, DisjointReactor<3>>(parse_steps(input)?))
+}
+
+/// Parse one step per line, each with exactly `N` ranges.
+pub fn parse_steps<const N: usize>(input: &str) -> Result<Vec<RebootStep<N>>> {
+    input
+        .split('\n')
+        .map(|line| RebootStep::from_input(input, line))
+        .collect()
+}
+
+/// A position with a coordinate for each of `N` axes.
+pub type Point<const N: usize> = [isize; N];
+
+#[derive(Debug, Clone)]
+pub struct RebootStep<const N: usize> {
+    pub cuboid: Cuboid<N>,
+    pub on_off: OnOff,
+}
+impl<const N: usize> RebootStep<N> {
+    /// Parse the step on `line`, a subslice of the full puzzle `input`. The
+    /// step is followed by `N` comma separated `axis=min..max` ranges; the
+    /// axis names are not checked.
+    fn from_input(input: &str, line: &str) -> Result<Self> {
+        let (on_off, rest) = parse::split_once(input, line, " ")?;
+        let on_off = match on_off {
+            "on" => OnOff::On,
+            "off" => OnOff::Off,
+            _ => return Err(AocError::at(input, on_off, "expected \"on\" or \"off\"")),
+        };
+        let ranges: Vec<&str> = rest.split(',').collect();
+        if ranges.len() != N {
+            return Err(AocError::at(
+                input,
+                rest,
+                format!("expected {N} ranges, found {}", ranges.len()),
+            ));
+        }
+        let (mut min, mut max) = ([0; N], [0; N]);
+        for (axis, r) in ranges.into_iter().enumerate() {
+            let (_, range) = parse::split_once(input, r, "=")?;
+            let (lo_text, hi_text) = parse::split_once(input, range, "..")?;
+            let lo: isize = parse::parse(input, lo_text)?;
+            let hi: isize = parse::parse(input, hi_text)?;
+            if lo > hi {
+                return Err(AocError::at(input, range, "range is empty"));
+            }
+            // Cuboids exclude their upper bound, which must also fit.
+            let end = hi.checked_add(1).ok_or_else(|| {
+                AocError::at(
+                    input,
+                    hi_text,
+                    format!("range must end before {}", isize::MAX),
+                )
+            })?;
+            (min[axis], max[axis]) = (lo, end);
+        }
+        let cuboid = Cuboid::new(min, max);
+        Ok(Self { cuboid, on_off })
+    }
+}
+
+/// The cubes from `min` up to but not including `max` on every axis.
+#[derive(Debug, Clone)]
+pub struct Cuboid<const N: usize> {
+    min: Point<N>,
+    max: Point<N>,
+}
+
+impl<const N: usize> Cuboid<N> {
+    /// Panics unless `min` is less than `max` on every axis.
+    pub fn new(min: Point<N>, max: Point<N>) -> Self {
+        assert!((0..N).all(|axis| min[axis] < max[axis]));
+        Cuboid { min, max }
+    }
+
+    pub fn min(&self) -> Point<N> {
+        self.min
+    }
+    pub fn max(&self) -> Point<N> {
+        self.max
+    }
+    pub fn contains(&self, point: Point<N>) -> bool {
+        (0..N).all(|axis| (self.min[axis]..self.max[axis]).contains(&point[axis]))
+    }
+    pub fn volume(&self) -> usize {
+        (0..N)
+            .map(|axis| self.min[axis].abs_diff(self.max[axis]))
+            .product()
+    }
+    /// The parts of this cuboid outside of `rhs`, as at most `2 * N`
+    /// disjoint cuboids. `rhs` must overlap this cuboid.
+    fn difference(&self, rhs: &Self) -> Vec<Self> {
+        let mut pieces = Vec::new();
+        // Slice off the slabs on either side of `rhs` along each axis in turn,
+        // shrinking what remains to the overlap on that axis.
+        let mut rest = self.clone();
+        for axis in 0..N {
+            if rest.min[axis] < rhs.min[axis] {
+                let mut below = rest.clone();
+                below.max[axis] = rhs.min[axis];
+                rest.min[axis] = rhs.min[axis];
+                pieces.push(below);
+            }
+            if rhs.max[axis] < rest.max[axis] {
+                let mut above = rest.clone();
+                above.min[axis] = rhs.max[axis];
+                rest.max[axis] = rhs.max[axis];
+                pieces.push(above);
+            }
+        }
+        pieces
+    }
+
+    pub fn intersection(&self, rhs: &Self) -> Option<Self> {
+        let min = std::array::from_fn(|axis| self.min[axis].max(rhs.min[axis]));
+        let max = std::array::from_fn(|axis| self.max[axis].min(rhs.max[axis]));
+        if (0..N).all(|axis| min[axis] < max[axis]) {
+            Some(Cuboid::new(min, max))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnOff {
+    On,
+    Off,
+}
+impl OnOff {
+    fn invert(self) -> Self {
+        match self {
+            OnOff::On => OnOff::Off,
+            OnOff::Off => OnOff::On,
+        }
+    }
+    fn is_on(self) -> bool {
+        matches!(self, OnOff::On)
+    }
+}
+
+/// Tracks which cubes are on as reboot steps are applied.
+pub trait Reactor<const N: usize>: Default {
+    fn follow_reboot_step(&mut self, step: RebootStep<N>);
+    fn num_cubes_on(&self) -> usize;
+    fn is_on(&self, cube: Point<N>) -> bool;
+    /// The number of cubes within `region` that are on.
+    fn count_on_in(&self, region: &Cuboid<N>) -> usize;
+}
+
+/// Records every step as signed cuboids whose volumes sum to the number of
+/// cubes on. Each step adds a cancelling cuboid for every existing cuboid it
+/// overlaps, so the list can grow exponentially when steps overlap heavily.
+#[derive(Debug)]
+pub struct InclusionExclusionReactor<const N: usize> {
+    cuboids: Vec<(Cuboid<N>, OnOff)>,
+}
+impl<const N: usize> Default for InclusionExclusionReactor<N> {
+    fn default() -> Self {
+        InclusionExclusionReactor {
+            cuboids: Vec::new(),
+        }
+    }
+}
+impl<const N: usize> Reactor<N> for InclusionExclusionReactor<N> {
+    fn follow_reboot_step(&mut self, step: RebootStep<N>) {
+        for idx in 0..self.cuboids.len() {
+            let (curr_cuboid, curr_onoff) = &self.cuboids[idx];
+            if let Some(overlap) = step.cuboid.intersection(curr_cuboid) {
+                let inverted = curr_onoff.invert();
+                self.cuboids.push((overlap, inverted))
+            }
+        }
+        if step.on_off.is_on() {
+            self.cuboids.push((step.cuboid, step.on_off))
+        }
+    }
+
+    fn num_cubes_on(&self) -> usize {
+        let volume: isize = self
+            .cuboids
+            .iter()
+            .map(|(cuboid, on_off)| match on_off {
+                OnOff::On => cuboid.volume() as isize,
+                OnOff::Off => -(cuboid.volume() as isize),
+            })
+            .sum();
+        volume as usize
+    }
+
+    fn is_on(&self, cube: Point<N>) -> bool {
+        let count: isize = self
+            .cuboids
+            .iter()
+            .filter(|(cuboid, _)| cuboid.contains(cube))
+            .map(|(_, on_off)| if on_off.is_on() { 1 } else { -1 })
+            .sum();
+        count > 0
+    }
+
+    fn count_on_in(&self, region: &Cuboid<N>) -> usize {
+        let volume: isize = self
+            .cuboids
+            .iter()
+            .filter_map(|(cuboid, on_off)| {
+                let volume = cuboid.intersection(region)?.volume() as isize;
+                Some(if on_off.is_on() { volume } else { -volume })
+            })
+            .sum();
+        volume as usize
+    }
+}
+
+/// Keeps the cubes that are on as a set of non-overlapping cuboids. Each step
+/// carves its cuboid out of every cuboid it overlaps, leaving at most `2 * N`
+/// pieces of each behind.
+#[derive(Debug)]
+pub struct DisjointReactor<const N: usize> {
+    cuboids: Vec<Cuboid<N>>,
+}
+impl<const N: usize> Default for DisjointReactor<N> {
+    fn default() -> Self {
+        DisjointReactor {
+            cuboids: Vec::new(),
+        }
+    }
+}
+impl<const N: usize> Reactor<N> for DisjointReactor<N> {
+    fn follow_reboot_step(&mut self, step: RebootStep<N>) {
+        let mut cuboids = Vec::with_capacity(self.cuboids.len());
+        for cuboid in self.cuboids.drain(..) {
+            if cuboid.intersection(&step.cuboid).is_some() {
+                cuboids.extend(cuboid.difference(&step.cuboid));
+            } else {
+                cuboids.push(cuboid);
+            }
+        }
+        if step.on_off.is_on() {
+            cuboids.push(step.cuboid);
+        }
+        self.cuboids = cuboids;
+    }
+
+    fn num_cubes_on(&self) -> usize {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    fn is_on(&self, cube: Point<N>) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(cube))
+    }
+
+    fn count_on_in(&self, region: &Cuboid<N>) -> usize {
+        self.cuboids
+            .iter()
+            .filter_map(|cuboid| cuboid.intersection(region))
+            .map(|overlap| overlap.volume())
+            .sum()
+    }
+}
+
+impl<const N: usize> DisjointReactor<N> {
+    /// The cubes that are on, as non-overlapping cuboids in no particular
+    /// order.
+    pub fn lit_cuboids(&self) -> impl Iterator<Item = &Cuboid<N>> + '_ {
+        self.cuboids.iter()
+    }
+}
+
+/// Follow every step, starting from a reactor with every cube off.
+pub fn reboot<const N: usize, R: Reactor<N>>(
+    reboot_steps: impl IntoIterator<Item = RebootStep<N>>,
+) -> R {
+    let mut reactor = R::default();
+    for step in reboot_steps {
+        reactor.follow_reboot_step(step);
+    }
+    reactor
+}
+
+pub fn solve<const N: usize, R: Reactor<N>>(
+    reboot_steps: impl IntoIterator<Item = RebootStep<N>>,
+) -> usize {
+    reboot::<N, R>(reboot_steps).num_cubes_on()
+}
+
+#[test]
+fn test_reactors_agree() {
+    let input = "on x=-20..26,y=-36..17,z=-47..7
+on x=-20..33,y=-21..23,z=-26..28
+on x=-22..28,y=-29..23,z=-38..16
+off x=-48..-32,y=26..41,z=-47..-37
+on x=-12..35,y=6..50,z=-50..-2
+off x=-48..-32,y=-32..-16,z=-15..-5
+on x=-18..26,y=-33..15,z=-7..46
+off x=-40..-22,y=-38..-28,z=23..41
+on x=-16..35,y=-41..10,z=-47..6
+off x=-32..-23,y=11..30,z=-14..3";
+    let steps = parse_steps(input).unwrap();
+    let signed: InclusionExclusionReactor<3> = reboot(steps.clone());
+    let disjoint: DisjointReactor<3> = reboot(steps);
+    assert_eq!(signed.num_cubes_on(), disjoint.num_cubes_on());
+    assert_eq!(
+        disjoint.lit_cuboids().map(Cuboid::volume).sum::<usize>(),
+        disjoint.num_cubes_on()
+    );
+
+    let region = Cuboid::new([-30, -5, 0], [10, 20, 30]);
+    assert_eq!(signed.count_on_in(&region), disjoint.count_on_in(&region));
+    for cube in [[0, 0, 0], [-45, 30, -40], [-30, 20, -5]] {
+        assert_eq!(signed.is_on(cube), disjoint.is_on(cube));
+    }
+    assert!(disjoint.is_on([0, 0, 0]));
+
+    // The same problem in 2 and 4 dimensions.
+    let steps: Vec<RebootStep<2>> = parse_steps("on x=0..9,y=0..9\noff x=5..14,y=5..14").unwrap();
+    assert_eq!(solve::<2, DisjointReactor<2>>(steps.clone()), 75);
+    assert_eq!(solve::<2, InclusionExclusionReactor<2>>(steps), 75);
+    let steps: Vec<RebootStep<4>> =
+        parse_steps("on x=0..1,y=0..1,z=0..1,w=0..1\noff x=1..1,y=1..1,z=1..1,w=1..1").unwrap();
+    assert_eq!(solve::<4, DisjointReactor<4>>(steps), 15);
+    assert!(parse_steps::<3>("on x=0..1,y=0..1").is_err());
+
+    let err = |input| {
+        let err = parse_steps::<2>(input).unwrap_err();
+        (err.message().to_string(), err.pos().map(|pos| pos.column))
+    };
+    assert_eq!(
+        err("on x=0..9223372036854775807,y=0..1"),
+        (format!("range must end before {}", isize::MAX), Some(9))
+    );
+    assert_eq!(
+        err("on x=0..1,y=2..1"),
+        ("range is empty".to_string(), Some(13))
+    );
+}
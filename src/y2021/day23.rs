@@ -0,0 +1,512 @@
+use std::{fmt::Write, ops::Range};
+
+use crate::{
+    error::{AocError, Result},
+    search::{self, Graph},
+};
+
+use aoc_runner_derive::aoc;
+
+// Example Cave:
+// #  #  #  #  #  #  #  #  #  #  #  #  #
+// #  .  .  .  .  .  .  .  .  .  .  .  #
+// #  #  #  B  #  C  #  B  #  D  #  #  #
+//       #  D  #  C  #  B  #  A  #
+//       #  D  #  B  #  A  #  C  #
+//       #  A  #  D  #  C  #  A  #
+//       #  #  #  #  #  #  #  #  #
+//
+// In code the tiles will be indexed as
+//
+// #  #  #  #  #  #  #  #  #  #  #  #  #
+// #  0  1  2  3  4  5  6  7  8  9  10 #
+// #  #  #  11 #  15 #  19 #  23 #  #  #
+//       #  12 #  16 #  20 #  24 #
+//       #  13 #  17 #  21 #  25 #
+//       #  14 #  18 #  22 #  26 #
+//       #  #  #  #  #  #  #  #  #
+//
+// The hallway comes first, followed by each room from top to bottom. The
+// hallway tiles directly above a room are doors, where amphipods may pass
+// but never stop. Burrows can have any number of rooms of any depth below a
+// hallway of any length; room `i` belongs to the `i`th species, lettered
+// from 'A'.
+
+/// The empty contents of a tile. Otherwise a tile holds one more than the
+/// species of its amphipod.
+const EMPTY: u8 = 0;
+
+/// The rows part 2 unfolds between the first and second rows of each room.
+const FOLDED_ROWS: [&str; 2] = ["DCBA", "DBAC"];
+
+/// The shape of a burrow and the energy each species uses per step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burrow {
+    hallway_len: usize,
+    /// The hallway tile above each room, from left to right.
+    doors: Vec<usize>,
+    depth: usize,
+    /// The energy used by each species to take one step.
+    energies: Vec<usize>,
+}
+
+/// The contents of every tile of a burrow.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cave(Box<[u8]>);
+
+impl Cave {
+    /// The species of the amphipod on tile `idx`, if there is one.
+    pub fn species(&self, idx: usize) -> Option<usize> {
+        match self.0[idx] {
+            EMPTY => None,
+            tile => Some(tile as usize - 1),
+        }
+    }
+
+    pub fn num_tiles(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Burrow {
+    /// Parse a burrow diagram. Species use 1, 10, 100, ... energy per step
+    /// in letter order.
+    pub fn parse(input: &str) -> Result<(Burrow, Cave)> {
+        if let Some(idx) = input.find(|c: char| !c.is_ascii()) {
+            return Err(AocError::at_offset(input, idx, "unexpected character"));
+        }
+        let lines: Vec<&str> = input.lines().collect();
+        let [top, hallway, rooms @ ..] = &lines[..] else {
+            return Err(AocError::new("expected a hallway and rooms"));
+        };
+        if let Some(idx) = top.find(|c| c != '#') {
+            return Err(AocError::at(input, &top[idx..], "expected a wall"));
+        }
+        let hallway = hallway
+            .strip_prefix('#')
+            .and_then(|hallway| hallway.strip_suffix('#'))
+            .ok_or_else(|| AocError::at(input, hallway, "expected a hallway between walls"))?;
+
+        // Every row of the rooms has tiles in the same columns, followed by
+        // a wall along the bottom.
+        let is_tile = |c| c != '#' && c != ' ';
+        let depth = rooms
+            .iter()
+            .take_while(|line| line.contains(is_tile))
+            .count();
+        let columns: Vec<usize> = match rooms.first() {
+            Some(line) if depth > 0 => line.match_indices(is_tile).map(|(idx, _)| idx).collect(),
+            _ => {
+                return Err(AocError::at(
+                    input,
+                    &input[input.len()..],
+                    "expected a room",
+                ))
+            }
+        };
+        if columns.len() > 26 {
+            return Err(AocError::at(input, rooms[0], "more rooms than species"));
+        }
+        if depth == rooms.len() {
+            return Err(AocError::at(
+                input,
+                &input[input.len()..],
+                "expected a wall",
+            ));
+        }
+        for (row, line) in rooms.iter().enumerate() {
+            let expected = if row < depth { &columns[..] } else { &[] };
+            for column in 0..line.len().max(expected.last().map_or(0, |last| last + 1)) {
+                let fragment = line.get(column..column + 1).unwrap_or(&line[line.len()..]);
+                let msg = match (fragment.contains(is_tile), expected.contains(&column)) {
+                    (true, false) => "expected a wall",
+                    (false, true) => "expected a tile",
+                    _ => continue,
+                };
+                return Err(AocError::at(input, fragment, msg));
+            }
+        }
+        if let Some(&column) = columns
+            .iter()
+            .find(|&&column| column == 0 || column > hallway.len())
+        {
+            return Err(AocError::at(
+                input,
+                &rooms[0][column..column + 1],
+                "room is not below the hallway",
+            ));
+        }
+
+        let burrow = Burrow {
+            hallway_len: hallway.len(),
+            doors: columns.iter().map(|column| column - 1).collect(),
+            depth,
+            energies: (0..columns.len() as u32)
+                .map(|species| 10usize.saturating_pow(species))
+                .collect(),
+        };
+        let mut tiles = vec![EMPTY; burrow.num_tiles()];
+        for (idx, tile) in tiles[..burrow.hallway_len].iter_mut().enumerate() {
+            *tile = burrow.parse_tile(input, &hallway[idx..idx + 1])?;
+        }
+        for (room, &column) in columns.iter().enumerate() {
+            for (row, line) in rooms[..depth].iter().enumerate() {
+                tiles[burrow.room_tile(room, row)] =
+                    burrow.parse_tile(input, &line[column..column + 1])?;
+            }
+        }
+        let cave = Cave(tiles.into());
+
+        for species in 0..burrow.num_rooms() {
+            let count = (0..cave.num_tiles())
+                .filter(|&idx| cave.species(idx) == Some(species))
+                .count();
+            if count != depth {
+                return Err(AocError::new(format!(
+                    "expected {depth} amphipods of species {}, found {count}",
+                    species_char(species)
+                )));
+            }
+        }
+        Ok((burrow, cave))
+    }
+
+    /// Parse the single character `tile`.
+    fn parse_tile(&self, input: &str, tile: &str) -> Result<u8> {
+        let last = species_char(self.num_rooms() - 1);
+        match tile.as_bytes()[0] {
+            b'.' => Ok(EMPTY),
+            b @ b'A'..=b'Z' if b as char <= last => Ok(b - b'A' + 1),
+            _ => Err(AocError::at(
+                input,
+                tile,
+                format!("expected '.' or 'A'-'{last}'"),
+            )),
+        }
+    }
+
+    /// Use `energies[i]` as the energy of a step by species `i`. There must
+    /// be one energy per species.
+    pub fn with_energies(mut self, energies: Vec<usize>) -> Result<Self> {
+        if energies.len() != self.num_rooms() {
+            return Err(AocError::new(format!(
+                "expected {} energies, one per species, found {}",
+                self.num_rooms(),
+                energies.len()
+            )));
+        }
+        self.energies = energies;
+        Ok(self)
+    }
+
+    pub fn hallway_len(&self) -> usize {
+        self.hallway_len
+    }
+
+    pub fn num_rooms(&self) -> usize {
+        self.doors.len()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn num_tiles(&self) -> usize {
+        self.hallway_len + self.num_rooms() * self.depth
+    }
+
+    /// The index of the tile `row` tiles down from the top of `room`.
+    pub fn room_tile(&self, room: usize, row: usize) -> usize {
+        self.hallway_len + room * self.depth + row
+    }
+
+    /// The cave with every amphipod at the bottom of its own room.
+    pub fn goal(&self) -> Cave {
+        let mut tiles = vec![EMPTY; self.num_tiles()];
+        for room in 0..self.num_rooms() {
+            for row in 0..self.depth {
+                tiles[self.room_tile(room, row)] = room as u8 + 1;
+            }
+        }
+        Cave(tiles.into())
+    }
+
+    /// Insert the two rows folded away in part 2 below the first row of
+    /// each room.
+    pub fn unfold(&self, cave: &Cave) -> Result<(Burrow, Cave)> {
+        if self.num_rooms() != FOLDED_ROWS[0].len() {
+            return Err(AocError::new(format!(
+                "only burrows of {} rooms can be unfolded",
+                FOLDED_ROWS[0].len()
+            )));
+        }
+        let unfolded = Burrow {
+            depth: self.depth + FOLDED_ROWS.len(),
+            ..self.clone()
+        };
+        let mut tiles = cave.0[..self.hallway_len].to_vec();
+        for room in 0..self.num_rooms() {
+            let rows = &cave.0[self.room_tile(room, 0)..self.room_tile(room + 1, 0)];
+            tiles.push(rows[0]);
+            tiles.extend(FOLDED_ROWS.map(|row| row.as_bytes()[room] - b'A' + 1));
+            tiles.extend(&rows[1..]);
+        }
+        Ok((unfolded, Cave(tiles.into())))
+    }
+
+    /// The lowest energy plan for organizing the amphipods, if they can be.
+    pub fn solve(&self, cave: &Cave) -> Option<Plan> {
+        let goal = self.goal();
+        let path = search::dijkstra_path(self, cave.clone(), |cave| *cave == goal)?;
+        let moves = path
+            .steps
+            .windows(2)
+            .map(|steps| {
+                let [(before, before_energy), (after, after_energy)] = steps else {
+                    unreachable!("windows of 2");
+                };
+                // Exactly one tile is vacated and one is filled by each move.
+                let changed = |is_from: bool| {
+                    (0..self.num_tiles())
+                        .find(|&idx| {
+                            before.species(idx).is_some() == is_from
+                                && after.species(idx).is_some() != is_from
+                        })
+                        .expect("every move changes two tiles")
+                };
+                Move {
+                    from: changed(true),
+                    to: changed(false),
+                    energy: after_energy - before_energy,
+                }
+            })
+            .collect();
+        Some(Plan {
+            energy: path.cost,
+            caves: path.nodes().cloned().collect(),
+            moves,
+        })
+    }
+
+    /// Draw the cave as a burrow diagram, in the same format it is parsed
+    /// from.
+    pub fn diagram(&self, cave: &Cave) -> String {
+        let tile = |idx| cave.species(idx).map_or('.', species_char);
+        let width = self.hallway_len + 2;
+        let mut diagram = "#".repeat(width) + "\n#";
+        diagram.extend((0..self.hallway_len).map(tile));
+        diagram += "#\n";
+
+        // Below the top row the walls only surround the rooms.
+        let left = self.doors[0];
+        let right = self.doors[self.num_rooms() - 1] + 2;
+        let room_below = |column| self.doors.iter().position(|&door| door + 1 == column);
+        for row in 0..=self.depth {
+            let line: String = (0..width)
+                .map(|column| match room_below(column) {
+                    Some(room) if row < self.depth => tile(self.room_tile(room, row)),
+                    _ if row == 0 || (left..=right).contains(&column) => '#',
+                    _ => ' ',
+                })
+                .collect();
+            writeln!(diagram, "{}", line.trim_end()).unwrap();
+        }
+        diagram
+    }
+
+    /// Where the amphipod on tile `idx` can move to and the energy it uses.
+    fn moves_from(&self, cave: &Cave, idx: usize) -> Vec<(usize, usize)> {
+        let Some(species) = cave.species(idx) else {
+            return Vec::new();
+        };
+        let energy = self.energies[species];
+        let clear = |mut tiles: Range<usize>| tiles.all(|idx| cave.0[idx] == EMPTY);
+
+        if idx < self.hallway_len {
+            return self
+                .path_home(cave, species, idx)
+                .map(|(tile, steps)| (tile, steps * energy))
+                .into_iter()
+                .collect();
+        }
+
+        let room = (idx - self.hallway_len) / self.depth;
+        let row = (idx - self.hallway_len) % self.depth;
+        let top = self.room_tile(room, 0);
+        let bottom = self.room_tile(room + 1, 0);
+        if !clear(top..idx) {
+            // Blocked from leaving the room.
+            return Vec::new();
+        }
+        if room == species && (idx + 1..bottom).all(|tile| cave.species(tile) == Some(species)) {
+            // Already settled in its own room.
+            return Vec::new();
+        }
+        let door = self.doors[room];
+        // Walk out along the hallway in both directions until blocked, or
+        // straight into the destination room if it's ready.
+        let empty = |&tile: &usize| cave.0[tile] == EMPTY;
+        (0..door)
+            .rev()
+            .take_while(empty)
+            .chain((door + 1..self.hallway_len).take_while(empty))
+            .filter(|tile| !self.doors.contains(tile))
+            .map(|tile| (tile, (row + 1 + tile.abs_diff(door)) * energy))
+            .chain(
+                self.path_home(cave, species, door)
+                    .map(|(tile, steps)| (tile, (row + 1 + steps) * energy)),
+            )
+            .collect()
+    }
+
+    /// The tile an amphipod of `species` in hallway tile `from` moves to in
+    /// its destination room, and the number of steps it takes. It only moves
+    /// in once the room holds no other species, and then as deep as possible.
+    fn path_home(&self, cave: &Cave, species: usize, from: usize) -> Option<(usize, usize)> {
+        let door = self.doors[species];
+        let room = self.room_tile(species, 0)..self.room_tile(species + 1, 0);
+        if room
+            .clone()
+            .any(|tile| cave.species(tile).is_some_and(|other| other != species))
+        {
+            return None;
+        }
+        let row = room
+            .take_while(|&tile| cave.0[tile] == EMPTY)
+            .count()
+            .checked_sub(1)?;
+        let mut path = if from < door {
+            from + 1..door + 1
+        } else {
+            door..from
+        };
+        if !path.all(|tile| cave.0[tile] == EMPTY) {
+            return None;
+        }
+        let steps = from.abs_diff(door) + row + 1;
+        Some((self.room_tile(species, row), steps))
+    }
+}
+
+impl Graph for Burrow {
+    type Node = Cave;
+
+    fn neighbors(&self, cave: &Cave) -> impl Iterator<Item = (Cave, usize)> {
+        (0..self.num_tiles()).flat_map(move |from| {
+            self.moves_from(cave, from)
+                .into_iter()
+                .map(move |(to, energy)| {
+                    let mut next = cave.clone();
+                    next.0.swap(from, to);
+                    (next, energy)
+                })
+        })
+    }
+}
+
+fn species_char(species: usize) -> char {
+    (b'A' + species as u8) as char
+}
+
+#[aoc(day23, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(part1_plan(input)?.energy)
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    Ok(part2_plan(input)?.energy)
+}
+
+/// The lowest energy plan for organizing the amphipods in part 1.
+pub fn part1_plan(input: &str) -> Result<Plan> {
+    let (burrow, cave) = Burrow::parse(input)?;
+    solve(&burrow, &cave)
+}
+
+/// The lowest energy plan for organizing the amphipods in part 2.
+pub fn part2_plan(input: &str) -> Result<Plan> {
+    let (burrow, cave) = Burrow::parse(input)?;
+    let (burrow, cave) = burrow.unfold(&cave)?;
+    solve(&burrow, &cave)
+}
+
+fn solve(burrow: &Burrow, cave: &Cave) -> Result<Plan> {
+    burrow
+        .solve(cave)
+        .ok_or_else(|| AocError::new("the amphipods can't be organized"))
+}
+
+/// A single amphipod moving between two tiles. Tiles use the indexes shown in
+/// the diagram at the top of this file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub from: usize,
+    pub to: usize,
+    pub energy: usize,
+}
+
+/// A sequence of moves that organizes the amphipods.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    /// The total energy required.
+    pub energy: usize,
+    /// Every state of the cave, from the starting arrangement to the organized
+    /// one.
+    pub caves: Vec<Cave>,
+    /// The moves between consecutive states in `caves`.
+    pub moves: Vec<Move>,
+}
+
+#[test]
+fn test_burrows() {
+    let example = "#############\n\
+                   #...........#\n\
+                   ###B#C#B#D###\n  \
+                     #A#D#C#A#\n  \
+                     #########";
+    let (burrow, cave) = Burrow::parse(example).unwrap();
+    assert_eq!(burrow.diagram(&cave), example.to_string() + "\n");
+    assert_eq!((burrow.num_rooms(), burrow.depth()), (4, 2));
+    assert_eq!(burrow.solve(&cave).unwrap().energy, 12521);
+
+    let (unfolded, cave) = burrow.unfold(&cave).unwrap();
+    assert_eq!(unfolded.diagram(&cave).lines().nth(3), Some("  #D#C#B#A#"));
+
+    // Three rooms of depth 1 with space to spare at the ends of the hallway.
+    let small = "###########\n\
+                 #.........#\n\
+                 ###C#B#A###\n  \
+                   #######";
+    let (burrow, cave) = Burrow::parse(small).unwrap();
+    assert_eq!(
+        burrow.goal(),
+        Burrow::parse(&small.replace("C#B#A", "A#B#C")).unwrap().1
+    );
+    // C waits in the hallway while A steps out of its way.
+    assert_eq!(burrow.solve(&cave).unwrap().energy, 8 + 6 * 100);
+    // When C moves for free A no longer needs to step aside.
+    assert_eq!(
+        burrow
+            .clone()
+            .with_energies(vec![1, 1])
+            .unwrap_err()
+            .message(),
+        "expected 3 energies, one per species, found 2"
+    );
+    let burrow = burrow.with_energies(vec![1, 1, 0]).unwrap();
+    let plan = burrow.solve(&cave).unwrap();
+    assert_eq!(plan.energy, 6);
+    assert_eq!(plan.caves.last(), Some(&burrow.goal()));
+
+    // Adjacent rooms: once A steps aside, B moves straight between rooms
+    // without stopping in the hallway.
+    let (burrow, cave) = Burrow::parse("######\n#....#\n##BA##\n ####").unwrap();
+    assert_eq!(burrow.solve(&cave).unwrap().energy, 2 + 3 * 10 + 3);
+
+    let err = Burrow::parse(&example.replace("#A#D", "#A#A")).unwrap_err();
+    assert_eq!(err.message(), "expected 2 amphipods of species A, found 3");
+    let err = Burrow::parse(&example.replace("#A#D", "#E#D")).unwrap_err();
+    assert_eq!(err.pos().map(|pos| (pos.line, pos.column)), Some((4, 4)));
+    assert!(Burrow::parse(&example.replace("#A#D", "#A.D")).is_err());
+}
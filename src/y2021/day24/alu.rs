@@ -0,0 +1,328 @@
+//! A general interpreter for ALU programs, so that model numbers can be
+//! checked by running MONAD itself rather than trusting its usual shape.
+
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
+use std::{error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reg {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Reg {
+    pub const ALL: [Reg; 4] = [Reg::W, Reg::X, Reg::Y, Reg::Z];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Reg(Reg),
+    Imm(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinOp {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instr {
+    /// Read the next input digit into the register.
+    Inp(Reg),
+    /// Store `dst <op> src` in `dst`.
+    Bin { op: BinOp, dst: Reg, src: Operand },
+}
+
+/// The values of the four registers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Registers([i64; 4]);
+
+impl Registers {
+    pub fn get(&self, reg: Reg) -> i64 {
+        self.0[reg.index()]
+    }
+
+    pub fn set(&mut self, reg: Reg, value: i64) {
+        self.0[reg.index()] = value;
+    }
+
+    fn operand(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Reg(reg) => self.get(reg),
+            Operand::Imm(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AluErrorKind {
+    /// `div` with a divisor of 0.
+    DivideByZero,
+    /// `mod` with a divisor of 0.
+    ModuloByZero,
+    /// `mod` of a negative value or by a negative divisor, which the ALU
+    /// doesn't define.
+    NegativeModulo { a: i64, b: i64 },
+    /// The result of `add` or `mul` does not fit in 64 bits.
+    Overflow,
+    /// `inp` ran out of input.
+    InputExhausted,
+}
+
+/// An error running a program, along with the index of the instruction
+/// that caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AluError {
+    pub kind: AluErrorKind,
+    pub instr: usize,
+}
+
+impl AluError {
+    pub fn new(kind: AluErrorKind, instr: usize) -> Self {
+        AluError { kind, instr }
+    }
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "instruction {}: ", self.instr)?;
+        match &self.kind {
+            AluErrorKind::DivideByZero => write!(f, "division by zero"),
+            AluErrorKind::ModuloByZero => write!(f, "modulo by zero"),
+            AluErrorKind::NegativeModulo { a, b } => write!(f, "{a} mod {b} is undefined"),
+            AluErrorKind::Overflow => write!(f, "overflowed"),
+            AluErrorKind::InputExhausted => write!(f, "no more input"),
+        }
+    }
+}
+
+impl error::Error for AluError {}
+
+impl BinOp {
+    /// Apply the operator, or the kind of error it fails with.
+    pub fn apply(self, a: i64, b: i64) -> Result<i64, AluErrorKind> {
+        match self {
+            BinOp::Add => a.checked_add(b).ok_or(AluErrorKind::Overflow),
+            BinOp::Mul => a.checked_mul(b).ok_or(AluErrorKind::Overflow),
+            BinOp::Div if b == 0 => Err(AluErrorKind::DivideByZero),
+            // Division truncates towards zero, as in Rust.
+            BinOp::Div => a.checked_div(b).ok_or(AluErrorKind::Overflow),
+            BinOp::Mod if b == 0 => Err(AluErrorKind::ModuloByZero),
+            BinOp::Mod if a < 0 || b < 0 => Err(AluErrorKind::NegativeModulo { a, b }),
+            BinOp::Mod => Ok(a % b),
+            BinOp::Eql => Ok((a == b).into()),
+        }
+    }
+}
+
+/// A parsed ALU program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    instrs: Vec<Instr>,
+}
+
+impl Program {
+    /// Parse one instruction per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        let instrs = input
+            .lines()
+            .map(|line| Instr::parse(input, line))
+            .collect::<Result<_>>()?;
+        Ok(Program { instrs })
+    }
+
+    pub fn new(instrs: Vec<Instr>) -> Self {
+        Program { instrs }
+    }
+
+    pub fn instrs(&self) -> &[Instr] {
+        &self.instrs
+    }
+
+    /// Run the program from all-zero registers, reading `inp` values from
+    /// `input` in order.
+    pub fn run(&self, input: impl IntoIterator<Item = i64>) -> Result<Registers, AluError> {
+        let mut input = input.into_iter();
+        let mut regs = Registers::default();
+        for (idx, &instr) in self.instrs.iter().enumerate() {
+            match instr {
+                Instr::Inp(reg) => {
+                    let value = input
+                        .next()
+                        .ok_or(AluError::new(AluErrorKind::InputExhausted, idx))?;
+                    regs.set(reg, value);
+                }
+                Instr::Bin { op, dst, src } => {
+                    let value = op
+                        .apply(regs.get(dst), regs.operand(src))
+                        .map_err(|kind| AluError::new(kind, idx))?;
+                    regs.set(dst, value);
+                }
+            }
+        }
+        Ok(regs)
+    }
+
+    /// Whether MONAD accepts `model_number`: it must be 14 digits, none of
+    /// them 0, and leave 0 in `z`.
+    pub fn accepts(&self, model_number: u64) -> Result<bool, AluError> {
+        let Some(digits) = model_digits(model_number) else {
+            return Ok(false);
+        };
+        Ok(self.run(digits)?.get(Reg::Z) == 0)
+    }
+}
+
+/// The number of digits in a model number.
+pub const MODEL_DIGITS: usize = 14;
+
+/// The digits of a 14 digit model number, most significant first, or `None`
+/// if it has a 0 digit or the wrong number of digits.
+pub fn model_digits(model_number: u64) -> Option<[i64; MODEL_DIGITS]> {
+    let mut digits = [0; MODEL_DIGITS];
+    let mut rest = model_number;
+    for digit in digits.iter_mut().rev() {
+        *digit = (rest % 10) as i64;
+        rest /= 10;
+    }
+    (rest == 0 && !digits.contains(&0)).then_some(digits)
+}
+
+impl Instr {
+    /// Parse a single instruction. `input` is the whole program, used to
+    /// locate errors.
+    pub fn parse(input: &str, line: &str) -> Result<Self> {
+        let mut words = line.split(' ');
+        let name = parse::next(input, &mut words, line, "an instruction")?;
+        let dst = parse_reg(input, parse::next(input, &mut words, line, "a register")?)?;
+        let op = match name {
+            "inp" => None,
+            "add" => Some(BinOp::Add),
+            "mul" => Some(BinOp::Mul),
+            "div" => Some(BinOp::Div),
+            "mod" => Some(BinOp::Mod),
+            "eql" => Some(BinOp::Eql),
+            _ => return Err(AocError::at(input, name, "unknown instruction")),
+        };
+        let instr = match op {
+            None => Instr::Inp(dst),
+            Some(op) => {
+                let src = parse::next(input, &mut words, line, "a register or value")?;
+                let src = match parse_reg(input, src) {
+                    Ok(reg) => Operand::Reg(reg),
+                    Err(_) => Operand::Imm(parse::parse(input, src)?),
+                };
+                Instr::Bin { op, dst, src }
+            }
+        };
+        if let Some(extra) = words.next() {
+            return Err(AocError::at(input, extra, "unexpected operand"));
+        }
+        Ok(instr)
+    }
+}
+
+fn parse_reg(input: &str, s: &str) -> Result<Reg> {
+    match s {
+        "w" => Ok(Reg::W),
+        "x" => Ok(Reg::X),
+        "y" => Ok(Reg::Y),
+        "z" => Ok(Reg::Z),
+        _ => Err(AocError::at(input, s, "expected a register")),
+    }
+}
+
+impl fmt::Display for Reg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Reg::W => "w",
+            Reg::X => "x",
+            Reg::Y => "y",
+            Reg::Z => "z",
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Reg(reg) => write!(f, "{reg}"),
+            Operand::Imm(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BinOp::Add => "add",
+            BinOp::Mul => "mul",
+            BinOp::Div => "div",
+            BinOp::Mod => "mod",
+            BinOp::Eql => "eql",
+        })
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instr::Inp(reg) => write!(f, "inp {reg}"),
+            Instr::Bin { op, dst, src } => write!(f, "{op} {dst} {src}"),
+        }
+    }
+}
+
+#[test]
+fn test_alu() {
+    // Store the 4 bits of the input in w, x, y and z.
+    let bits = Program::parse(
+        "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\n\
+         add x w\nmod x 2\ndiv w 2\nmod w 2",
+    )
+    .unwrap();
+    let regs = bits.run([0b1011]).unwrap();
+    assert_eq!(Reg::ALL.map(|reg| regs.get(reg)), [1, 0, 1, 1]);
+
+    let program = Program::parse("inp x\nmul x -1").unwrap();
+    assert_eq!(program.run([7]).unwrap().get(Reg::X), -7);
+    assert_eq!(program.instrs()[1].to_string(), "mul x -1");
+    assert_eq!(
+        program.run([]),
+        Err(AluError::new(AluErrorKind::InputExhausted, 0))
+    );
+
+    let program = Program::parse("inp w\ndiv z w\nmod z w").unwrap();
+    assert_eq!(
+        program.run([0]),
+        Err(AluError::new(AluErrorKind::DivideByZero, 1))
+    );
+    let program = Program::parse("inp w\nmod z w").unwrap();
+    assert_eq!(
+        program.run([0]),
+        Err(AluError::new(AluErrorKind::ModuloByZero, 1))
+    );
+
+    let err = Program::parse("inp w\nadd q 1").unwrap_err();
+    assert_eq!(err.pos().map(|pos| (pos.line, pos.column)), Some((2, 5)));
+    assert!(Program::parse("sub x 1").is_err());
+    assert!(Program::parse("add x").is_err());
+
+    assert_eq!(model_digits(13579246899999).map(|d| d[0]), Some(1));
+    assert_eq!(model_digits(13579246809999), None);
+    assert_eq!(model_digits(1357924689999), None);
+}
@@ -0,0 +1,342 @@
+//! Compile ALU programs to a shorter sequence of operations, so that model
+//! numbers can be checked in bulk.
+//!
+//! Compiling propagates constants through the program, folding any
+//! instruction whose operands are known and dropping those that leave their
+//! register unchanged, then removes instructions whose results are never
+//! read. Instructions that might fail are never removed, so a compiled
+//! program fails exactly when the original does. Finally each operation is
+//! specialized by the kind of its operand for a cheaper dispatch.
+//!
+//! `examples/alu_bench.rs` compares running a compiled program against the
+//! interpreter.
+
+use super::alu::{
+    model_digits, AluError, AluErrorKind, BinOp, Instr, Operand, Program, Reg, Registers,
+};
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Read the next input digit into the register.
+    Inp(Reg),
+    /// Store a constant in the register.
+    Set(Reg, i64),
+    /// Store the second register in the first.
+    Copy(Reg, Reg),
+    /// Store `dst <op> src` in `dst`.
+    Bin { op: BinOp, dst: Reg, src: Operand },
+}
+
+impl Op {
+    /// Whether running the operation can fail, other than by running out of
+    /// input.
+    fn may_fail(self) -> bool {
+        match self {
+            Op::Inp(_) | Op::Set(..) | Op::Copy(..) => false,
+            Op::Bin { op, src, .. } => match (op, src) {
+                (BinOp::Eql, _) => false,
+                (BinOp::Div, Operand::Imm(b)) => b == 0 || b == -1,
+                _ => true,
+            },
+        }
+    }
+}
+
+/// The operations of a compiled program, specialized by operand kind so
+/// that running each takes a single dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Code {
+    Inp(Reg),
+    Set(Reg, i64),
+    Copy(Reg, Reg),
+    AddImm(Reg, i64),
+    AddReg(Reg, Reg),
+    MulImm(Reg, i64),
+    MulReg(Reg, Reg),
+    /// Division by a constant other than 0 or -1, which can't fail.
+    DivImm(Reg, i64),
+    DivReg(Reg, Reg),
+    /// Modulo by a positive constant.
+    ModImm(Reg, i64),
+    ModReg(Reg, Reg),
+    EqlImm(Reg, i64),
+    EqlReg(Reg, Reg),
+    /// An `eql` immediately followed by `eql r 0`.
+    NeqImm(Reg, i64),
+    NeqReg(Reg, Reg),
+    /// Any other operation with a constant operand.
+    Other {
+        op: BinOp,
+        dst: Reg,
+        src: i64,
+    },
+}
+
+/// A compiled ALU program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compiled {
+    ops: Vec<Op>,
+    code: Vec<Code>,
+    /// The index of the instruction in the original program that each
+    /// operation in `code` came from, for reporting errors.
+    origins: Vec<usize>,
+}
+
+impl Program {
+    pub fn compile(&self) -> Compiled {
+        let (ops, origins) = eliminate_dead_code(fold_constants(self.instrs()));
+        let (code, origins) = lower(&ops, &origins);
+        Compiled { ops, code, origins }
+    }
+}
+
+impl Compiled {
+    /// The optimized operations, before being lowered for running.
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// Run the program from all-zero registers, reading `inp` values from
+    /// `input` in order. Errors refer to instructions of the original
+    /// program.
+    pub fn run(&self, input: impl IntoIterator<Item = i64>) -> Result<Registers, AluError> {
+        let mut input = input.into_iter();
+        let mut regs = [0; 4];
+        for (idx, &code) in self.code.iter().enumerate() {
+            let apply = |op: BinOp, a: i64, b: i64| {
+                op.apply(a, b)
+                    .map_err(|kind| AluError::new(kind, self.origins[idx]))
+            };
+            let (dst, value) = match code {
+                Code::Inp(dst) => match input.next() {
+                    Some(value) => (dst, value),
+                    None => {
+                        let kind = AluErrorKind::InputExhausted;
+                        return Err(AluError::new(kind, self.origins[idx]));
+                    }
+                },
+                Code::Set(dst, value) => (dst, value),
+                Code::Copy(dst, src) => (dst, regs[src as usize]),
+                Code::AddImm(dst, b) => (dst, apply(BinOp::Add, regs[dst as usize], b)?),
+                Code::AddReg(dst, src) => {
+                    let b = regs[src as usize];
+                    (dst, apply(BinOp::Add, regs[dst as usize], b)?)
+                }
+                Code::MulImm(dst, b) => (dst, apply(BinOp::Mul, regs[dst as usize], b)?),
+                Code::MulReg(dst, src) => {
+                    let b = regs[src as usize];
+                    (dst, apply(BinOp::Mul, regs[dst as usize], b)?)
+                }
+                Code::DivImm(dst, b) => (dst, regs[dst as usize] / b),
+                Code::DivReg(dst, src) => {
+                    let b = regs[src as usize];
+                    (dst, apply(BinOp::Div, regs[dst as usize], b)?)
+                }
+                Code::ModImm(dst, b) => (dst, apply(BinOp::Mod, regs[dst as usize], b)?),
+                Code::ModReg(dst, src) => {
+                    let b = regs[src as usize];
+                    (dst, apply(BinOp::Mod, regs[dst as usize], b)?)
+                }
+                Code::EqlImm(dst, b) => (dst, (regs[dst as usize] == b).into()),
+                Code::EqlReg(dst, src) => (dst, (regs[dst as usize] == regs[src as usize]).into()),
+                Code::NeqImm(dst, b) => (dst, (regs[dst as usize] != b).into()),
+                Code::NeqReg(dst, src) => (dst, (regs[dst as usize] != regs[src as usize]).into()),
+                Code::Other { op, dst, src } => (dst, apply(op, regs[dst as usize], src)?),
+            };
+            regs[dst as usize] = value;
+        }
+
+        let mut registers = Registers::default();
+        for reg in Reg::ALL {
+            registers.set(reg, regs[reg as usize]);
+        }
+        Ok(registers)
+    }
+
+    /// Whether the program accepts `model_number`, as for
+    /// [`Program::accepts`].
+    pub fn accepts(&self, model_number: u64) -> Result<bool, AluError> {
+        let Some(digits) = model_digits(model_number) else {
+            return Ok(false);
+        };
+        Ok(self.run(digits)?.get(Reg::Z) == 0)
+    }
+}
+
+/// Track which registers hold a known value, starting from all zeros, and
+/// rewrite each instruction using them.
+fn fold_constants(instrs: &[Instr]) -> Vec<(Op, usize)> {
+    let mut known = [Some(0); 4];
+    let mut ops = Vec::with_capacity(instrs.len());
+    for (idx, &instr) in instrs.iter().enumerate() {
+        let (op, dst, src) = match instr {
+            Instr::Inp(reg) => {
+                known[reg as usize] = None;
+                ops.push((Op::Inp(reg), idx));
+                continue;
+            }
+            Instr::Bin { op, dst, src } => (op, dst, src),
+        };
+        let a = known[dst as usize];
+        let b = match src {
+            Operand::Reg(reg) => known[reg as usize],
+            Operand::Imm(value) => Some(value),
+        };
+
+        let identity = matches!(
+            (op, b),
+            (BinOp::Add, Some(0)) | (BinOp::Mul | BinOp::Div, Some(1))
+        );
+        let folded = match (op, a, b) {
+            // Failures are left for run time.
+            (_, Some(a), Some(b)) => op.apply(a, b).ok().map(|value| Op::Set(dst, value)),
+            (BinOp::Mul, _, Some(0)) | (BinOp::Mul, Some(0), _) => Some(Op::Set(dst, 0)),
+            (BinOp::Add, Some(0), None) => match src {
+                Operand::Reg(src) => Some(Op::Copy(dst, src)),
+                Operand::Imm(_) => unreachable!("immediates are known"),
+            },
+            _ => None,
+        };
+        let op = match folded {
+            Some(Op::Set(_, value)) if a == Some(value) => continue,
+            Some(folded) => folded,
+            None if identity => continue,
+            None => Op::Bin {
+                op,
+                dst,
+                src: b.map_or(src, Operand::Imm),
+            },
+        };
+        known[dst as usize] = match op {
+            Op::Set(_, value) => Some(value),
+            Op::Copy(_, src) => known[src as usize],
+            _ => None,
+        };
+        ops.push((op, idx));
+    }
+    ops
+}
+
+/// Drop operations that can't fail and whose result is overwritten before
+/// it is read. Every register is read at the end of the program.
+fn eliminate_dead_code(ops: Vec<(Op, usize)>) -> (Vec<Op>, Vec<usize>) {
+    let mut live = [true; 4];
+    let mut kept = Vec::with_capacity(ops.len());
+    for (op, origin) in ops.into_iter().rev() {
+        let (dst, reads_dst, src) = match op {
+            Op::Inp(reg) => (reg, false, None),
+            Op::Set(reg, _) => (reg, false, None),
+            Op::Copy(dst, src) => (dst, false, Some(src)),
+            Op::Bin { dst, src, .. } => match src {
+                Operand::Reg(src) => (dst, true, Some(src)),
+                Operand::Imm(_) => (dst, true, None),
+            },
+        };
+        // Input is consumed whether or not it is used.
+        if !live[dst as usize] && !op.may_fail() && !matches!(op, Op::Inp(_)) {
+            continue;
+        }
+        live[dst as usize] = reads_dst;
+        if let Some(src) = src {
+            live[src as usize] = true;
+        }
+        kept.push((op, origin));
+    }
+    kept.into_iter().rev().unzip()
+}
+
+/// Specialize each operation for running, fusing `eql` followed by
+/// `eql r 0` into a single comparison.
+fn lower(ops: &[Op], origins: &[usize]) -> (Vec<Code>, Vec<usize>) {
+    let mut code = Vec::with_capacity(ops.len());
+    let mut code_origins = Vec::with_capacity(ops.len());
+    let mut idx = 0;
+    while idx < ops.len() {
+        let negated = matches!(
+            (ops[idx], ops.get(idx + 1)),
+            (
+                Op::Bin { op: BinOp::Eql, dst, .. },
+                Some(&Op::Bin {
+                    op: BinOp::Eql,
+                    dst: next,
+                    src: Operand::Imm(0),
+                }),
+            ) if dst == next
+        );
+        code.push(match ops[idx] {
+            Op::Inp(dst) => Code::Inp(dst),
+            Op::Set(dst, value) => Code::Set(dst, value),
+            Op::Copy(dst, src) => Code::Copy(dst, src),
+            Op::Bin { op, dst, src } => match (op, src) {
+                (BinOp::Add, Operand::Imm(b)) => Code::AddImm(dst, b),
+                (BinOp::Add, Operand::Reg(src)) => Code::AddReg(dst, src),
+                (BinOp::Mul, Operand::Imm(b)) => Code::MulImm(dst, b),
+                (BinOp::Mul, Operand::Reg(src)) => Code::MulReg(dst, src),
+                (BinOp::Div, Operand::Imm(b)) if b != 0 && b != -1 => Code::DivImm(dst, b),
+                (BinOp::Div, Operand::Reg(src)) => Code::DivReg(dst, src),
+                (BinOp::Mod, Operand::Imm(b)) if b > 0 => Code::ModImm(dst, b),
+                (BinOp::Mod, Operand::Reg(src)) => Code::ModReg(dst, src),
+                (BinOp::Eql, Operand::Imm(b)) if negated => Code::NeqImm(dst, b),
+                (BinOp::Eql, Operand::Reg(src)) if negated => Code::NeqReg(dst, src),
+                (BinOp::Eql, Operand::Imm(b)) => Code::EqlImm(dst, b),
+                (BinOp::Eql, Operand::Reg(src)) => Code::EqlReg(dst, src),
+                (op, Operand::Imm(src)) => Code::Other { op, dst, src },
+            },
+        });
+        code_origins.push(origins[idx]);
+        idx += if negated { 2 } else { 1 };
+    }
+    (code, code_origins)
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Inp(reg) => write!(f, "inp {reg}"),
+            Op::Set(reg, value) => write!(f, "set {reg} {value}"),
+            Op::Copy(dst, src) => write!(f, "copy {dst} {src}"),
+            Op::Bin { op, dst, src } => write!(f, "{op} {dst} {src}"),
+        }
+    }
+}
+
+impl fmt::Display for Compiled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for op in &self.ops {
+            writeln!(f, "{op}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_compile() {
+    let program = Program::parse(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 12\neql x w\neql x 0\n\
+         mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y 7\n\
+         mul y x\nadd z y",
+    )
+    .unwrap();
+    let compiled = program.compile();
+    assert_eq!(
+        compiled.to_string(),
+        "inp w\nset x 12\neql x w\neql x 0\nset y 25\nmul y x\nadd y 1\ncopy y w\n\
+         add y 7\nmul y x\ncopy z y\n"
+    );
+    for digit in 1..=9 {
+        assert_eq!(compiled.run([digit]), program.run([digit]));
+    }
+
+    // Failures are kept even when their result is unused, and are reported
+    // at the original instruction.
+    let program = Program::parse("inp w\nadd x 1\nmod x w\nmul x 0").unwrap();
+    let compiled = program.compile();
+    assert_eq!(compiled.to_string(), "inp w\nset x 1\nmod x w\nset x 0\n");
+    assert_eq!(
+        compiled.run([0]),
+        Err(AluError::new(AluErrorKind::ModuloByZero, 2))
+    );
+    assert_eq!(compiled.run([3]), program.run([3]));
+}
@@ -1,4 +1,19 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::error::{AocError, Result};
+
+use aoc_runner_derive::aoc;
+
+mod alu;
+mod compile;
+mod solver;
+mod template;
+
+pub use alu::{
+    model_digits, AluError, AluErrorKind, BinOp, Instr, Operand, Program, Reg, Registers,
+    MODEL_DIGITS,
+};
+pub use compile::{Compiled, Op};
+pub use solver::{max_model_number, min_model_number};
+pub use template::{validate, TemplateError};
 
 /*
 Input file is machine instructions. One per digit. Each digit is remarkably
@@ -159,48 +174,38 @@ w = prev_w + prev_immediate_15 + immediate_5
 */
 
 #[derive(Debug)]
-enum SubRoutine {
+pub enum SubRoutine {
     One { immediate_15: isize },
     Two { immediate_5: isize },
 }
 
-#[aoc_generator(day24)]
-fn parse(input: &str) -> Vec<SubRoutine> {
-    let mut subs = Vec::new();
-    let mut lines = input.split("\n");
-
-    while let Some("inp w") = lines.next() {
-        let div_instr = lines.nth(3).unwrap();
-        let immediate_5 = &lines.nth(0).unwrap()[6..];
-        let immediate_15 = &lines.nth(9).unwrap()[6..];
-        lines.nth(1).unwrap();
-
-        subs.push(match div_instr {
-            "div z 1" => SubRoutine::One {
-                immediate_15: immediate_15.parse().unwrap(),
-            },
-            "div z 26" => SubRoutine::Two {
-                immediate_5: immediate_5.parse().unwrap(),
-            },
-            _ => panic!("unrecognized subroutine"),
-        })
-    }
-    subs
+/// Parse MONAD into its subroutines, checking it follows the template.
+pub fn parse(input: &str) -> Result<Vec<SubRoutine>> {
+    template::validate(&Program::parse(input)?).map_err(|err| match err.instr_index() {
+        Some(idx) => {
+            let line = input.lines().nth(idx).unwrap_or(&input[input.len()..]);
+            AocError::at(input, line, err.to_string())
+        }
+        None => AocError::new(err.to_string()),
+    })
 }
 
+#[derive(Clone, Copy)]
 enum Part {
     One,
     Two,
 }
 
 fn solve(input: &[SubRoutine], part: Part) -> isize {
+    let place = |digit: usize| 10isize.pow((input.len() - 1 - digit) as u32);
     let mut result = 0;
     let mut stack = Vec::new();
-    for (digit, routine) in input.into_iter().enumerate() {
+    for (digit, routine) in input.iter().enumerate() {
         match routine {
             SubRoutine::One { immediate_15 } => stack.push((digit, immediate_15)),
             SubRoutine::Two { immediate_5 } => {
-                let (matching_digit, matching_immediate_15) = stack.pop().unwrap();
+                let (matching_digit, matching_immediate_15) =
+                    stack.pop().expect("validated pops have a matching push");
                 let offset = matching_immediate_15 + immediate_5;
 
                 let prev_w = match part {
@@ -208,8 +213,8 @@ fn solve(input: &[SubRoutine], part: Part) -> isize {
                     Part::Two => std::cmp::max(1, 1 - offset),
                 };
                 let w = prev_w + offset;
-                result += prev_w * 10isize.pow((13 - matching_digit) as u32);
-                result += w * 10isize.pow((13 - digit) as u32);
+                result += prev_w * place(matching_digit);
+                result += w * place(digit);
             }
         }
     }
@@ -217,11 +222,29 @@ fn solve(input: &[SubRoutine], part: Part) -> isize {
 }
 
 #[aoc(day24, part1)]
-fn part1(input: &[SubRoutine]) -> isize {
-    solve(input, Part::One)
+pub fn part1(input: &str) -> Result<u64> {
+    model_number(input, Part::One)
 }
 
 #[aoc(day24, part2)]
-fn part2(input: &[SubRoutine]) -> isize {
-    solve(input, Part::Two)
+pub fn part2(input: &str) -> Result<u64> {
+    model_number(input, Part::Two)
+}
+
+/// The largest (part 1) or smallest (part 2) accepted model number. The
+/// fast path is only taken when the input follows the template and MONAD
+/// accepts its answer; anything else falls back to searching.
+fn model_number(input: &str, part: Part) -> Result<u64> {
+    let program = Program::parse(input)?;
+    if let Ok(subs) = parse(input) {
+        let fast = solve(&subs, part) as u64;
+        if program.accepts(fast) == Ok(true) {
+            return Ok(fast);
+        }
+    }
+    let found = match part {
+        Part::One => max_model_number(&program),
+        Part::Two => min_model_number(&program),
+    };
+    found.ok_or_else(|| AocError::new("no model number is accepted"))
 }
@@ -0,0 +1,298 @@
+//! Find model numbers for any ALU program, without relying on MONAD
+//! following the usual template.
+//!
+//! The program is split into blocks that each start by reading a digit.
+//! Digits are chosen one block at a time, depth first, remembering every
+//! state that is known not to lead to an accepted number. States that
+//! interval analysis shows can't end with `z` at 0 are pruned without being
+//! explored.
+
+use super::alu::{AluErrorKind, BinOp, Instr, Operand, Program, Reg, Registers, MODEL_DIGITS};
+
+use std::collections::HashSet;
+
+/// The largest model number `program` accepts, if any.
+pub fn max_model_number(program: &Program) -> Option<u64> {
+    Solver::new(program).search([9, 8, 7, 6, 5, 4, 3, 2, 1])
+}
+
+/// The smallest model number `program` accepts, if any.
+pub fn min_model_number(program: &Program) -> Option<u64> {
+    Solver::new(program).search([1, 2, 3, 4, 5, 6, 7, 8, 9])
+}
+
+struct Solver<'a> {
+    /// The instructions before the first `inp`.
+    prelude: &'a [Instr],
+    /// The instructions from each `inp` up to the next.
+    blocks: Vec<&'a [Instr]>,
+    /// The registers read by each block, or by later blocks, before being
+    /// written. Other registers are cleared so equivalent states compare
+    /// equal.
+    live: Vec<[bool; 4]>,
+    /// States at the start of a block that can't lead to an accepted number.
+    dead: HashSet<(usize, Registers)>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(program: &'a Program) -> Self {
+        let instrs = program.instrs();
+        let starts: Vec<usize> = (0..instrs.len())
+            .filter(|&idx| matches!(instrs[idx], Instr::Inp(_)))
+            .collect();
+        let blocks: Vec<_> = starts
+            .iter()
+            .enumerate()
+            .map(|(block, &start)| {
+                let end = starts.get(block + 1).copied().unwrap_or(instrs.len());
+                &instrs[start..end]
+            })
+            .collect();
+
+        // Only z is read once the program is done.
+        let mut live = vec![[false, false, false, true]; blocks.len() + 1];
+        for (block, instrs) in blocks.iter().enumerate().rev() {
+            let mut regs = live[block + 1];
+            for instr in instrs.iter().rev() {
+                match *instr {
+                    Instr::Inp(reg) => regs[reg as usize] = false,
+                    Instr::Bin { op, dst, src } => {
+                        // `mul r 0` sets r without reading it.
+                        let reads_dst = !matches!((op, src), (BinOp::Mul, Operand::Imm(0)));
+                        regs[dst as usize] = reads_dst;
+                        if let Operand::Reg(src) = src {
+                            regs[src as usize] = true;
+                        }
+                    }
+                }
+            }
+            live[block] = regs;
+        }
+
+        Solver {
+            prelude: &instrs[..starts.first().copied().unwrap_or(instrs.len())],
+            blocks,
+            live,
+            dead: HashSet::new(),
+        }
+    }
+
+    /// The best model number, trying `digits` in order at each place. A
+    /// program without one block per digit accepts no model number.
+    fn search(&mut self, digits: [i64; 9]) -> Option<u64> {
+        if self.blocks.len() != MODEL_DIGITS {
+            return None;
+        }
+        let regs = run(self.prelude, Registers::default(), None).ok()?;
+        self.search_from(0, regs, digits)
+    }
+
+    /// The best number formed by the digits of the remaining blocks, given
+    /// the registers at the start of `block`.
+    fn search_from(&mut self, block: usize, mut regs: Registers, digits: [i64; 9]) -> Option<u64> {
+        for reg in Reg::ALL {
+            if !self.live[block][reg as usize] {
+                regs.set(reg, 0);
+            }
+        }
+        if block == self.blocks.len() {
+            return (regs.get(Reg::Z) == 0).then_some(0);
+        }
+        if self.dead.contains(&(block, regs)) || !self.may_accept(block, regs) {
+            return None;
+        }
+
+        let place = 10u64.pow((self.blocks.len() - block - 1) as u32);
+        for digit in digits {
+            let Ok(next) = run(self.blocks[block], regs, Some(digit)) else {
+                continue;
+            };
+            if let Some(rest) = self.search_from(block + 1, next, digits) {
+                return Some(digit as u64 * place + rest);
+            }
+        }
+        self.dead.insert((block, regs));
+        None
+    }
+
+    /// Whether interval analysis allows the rest of the program to finish
+    /// with `z` at 0 when started from `regs` at `block`.
+    fn may_accept(&self, block: usize, regs: Registers) -> bool {
+        let mut ranges = Reg::ALL.map(|reg| Range::point(regs.get(reg)));
+        for instr in self.blocks[block..].iter().copied().flatten() {
+            let (dst, range) = match *instr {
+                Instr::Inp(reg) => (reg, Some(Range { lo: 1, hi: 9 })),
+                Instr::Bin { op, dst, src } => {
+                    let src = match src {
+                        Operand::Reg(reg) => ranges[reg as usize],
+                        Operand::Imm(value) => Range::point(value),
+                    };
+                    (dst, Range::apply(op, ranges[dst as usize], src))
+                }
+            };
+            // Every path through the instruction fails.
+            let Some(range) = range else {
+                return false;
+            };
+            ranges[dst as usize] = range;
+        }
+        ranges[Reg::Z as usize].contains(0)
+    }
+}
+
+/// Run a block, feeding `digit` to its `inp`.
+fn run(
+    instrs: &[Instr],
+    mut regs: Registers,
+    digit: Option<i64>,
+) -> Result<Registers, AluErrorKind> {
+    for instr in instrs {
+        match *instr {
+            Instr::Inp(reg) => regs.set(reg, digit.ok_or(AluErrorKind::InputExhausted)?),
+            Instr::Bin { op, dst, src } => {
+                let src = match src {
+                    Operand::Reg(reg) => regs.get(reg),
+                    Operand::Imm(value) => value,
+                };
+                regs.set(dst, op.apply(regs.get(dst), src)?);
+            }
+        }
+    }
+    Ok(regs)
+}
+
+/// The inclusive range of values a register may hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Range {
+    lo: i64,
+    hi: i64,
+}
+
+impl Range {
+    const ALL: Range = Range {
+        lo: i64::MIN,
+        hi: i64::MAX,
+    };
+
+    fn point(value: i64) -> Self {
+        Range {
+            lo: value,
+            hi: value,
+        }
+    }
+
+    fn new(lo: i64, hi: i64) -> Option<Self> {
+        (lo <= hi).then_some(Range { lo, hi })
+    }
+
+    fn contains(self, value: i64) -> bool {
+        (self.lo..=self.hi).contains(&value)
+    }
+
+    /// The smallest range holding every value of `a <op> b` that doesn't
+    /// fail, or `None` if every value fails.
+    fn apply(op: BinOp, a: Range, b: Range) -> Option<Range> {
+        match op {
+            BinOp::Add | BinOp::Mul => Some(Range::from_corners(op, a, b)),
+            BinOp::Div => {
+                // Truncating division is monotonic in each operand as long
+                // as the divisor keeps its sign.
+                let negative = Range::new(b.lo, b.hi.min(-1));
+                let positive = Range::new(b.lo.max(1), b.hi);
+                [negative, positive]
+                    .into_iter()
+                    .flatten()
+                    .map(|b| Range::from_corners(op, a, b))
+                    .reduce(Range::union)
+            }
+            BinOp::Mod => {
+                let a = Range::new(a.lo.max(0), a.hi)?;
+                let b = Range::new(b.lo.max(1), b.hi)?;
+                if a.hi < b.lo {
+                    Some(a)
+                } else {
+                    Some(Range {
+                        lo: 0,
+                        hi: a.hi.min(b.hi - 1),
+                    })
+                }
+            }
+            BinOp::Eql => Some(if a.lo == a.hi && a == b {
+                Range::point(1)
+            } else if a.hi < b.lo || b.hi < a.lo {
+                Range::point(0)
+            } else {
+                Range { lo: 0, hi: 1 }
+            }),
+        }
+    }
+
+    /// The range spanned by applying a monotonic `op` to the corners of `a`
+    /// and `b`. Overflow widens the range to every value.
+    fn from_corners(op: BinOp, a: Range, b: Range) -> Range {
+        let mut corners = [(a.lo, b.lo), (a.lo, b.hi), (a.hi, b.lo), (a.hi, b.hi)]
+            .map(|(a, b)| op.apply(a, b).ok());
+        if corners.contains(&None) {
+            return Range::ALL;
+        }
+        corners.sort();
+        Range {
+            lo: corners[0].unwrap(),
+            hi: corners[3].unwrap(),
+        }
+    }
+
+    fn union(self, other: Range) -> Range {
+        Range {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+}
+
+#[test]
+fn test_solver() {
+    use super::SubRoutine;
+
+    // A program following the usual template, built from the immediates the
+    // fast path reads.
+    let subroutines = [
+        (1, 12, 7),
+        (1, 11, 15),
+        (1, 12, 2),
+        (26, -3, 15),
+        (1, 10, 14),
+        (26, -9, 2),
+        (1, 10, 15),
+        (26, -7, 1),
+        (26, -11, 15),
+        (26, -4, 15),
+        (1, 14, 12),
+        (1, 11, 2),
+        (26, -8, 13),
+        (26, -10, 13),
+    ];
+    let text = super::template::program_text(&subroutines);
+    let program = Program::parse(&text).unwrap();
+    let subs: Vec<SubRoutine> = super::parse(&text).unwrap();
+
+    let max = max_model_number(&program).unwrap();
+    let min = min_model_number(&program).unwrap();
+    assert_eq!(max as isize, super::solve(&subs, super::Part::One));
+    assert_eq!(min as isize, super::solve(&subs, super::Part::Two));
+    assert_eq!(program.accepts(max), Ok(true));
+    assert_eq!(program.accepts(min), Ok(true));
+
+    // Not the template at all: the digits must sum to 20.
+    let mut text = "inp w\nadd z w\n".repeat(14);
+    text += "add z -20";
+    let program = Program::parse(&text).unwrap();
+    assert_eq!(max_model_number(&program), Some(71111111111111));
+    assert_eq!(min_model_number(&program), Some(11111111111117));
+
+    assert_eq!(max_model_number(&Program::parse("inp z").unwrap()), None);
+    // Any two digits summing to 10 would do, but model numbers have 14.
+    let program = Program::parse(&("inp w\nadd z w\n".repeat(2) + "add z -10")).unwrap();
+    assert_eq!(max_model_number(&program), None);
+    assert_eq!(min_model_number(&program), None);
+}
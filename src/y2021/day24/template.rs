@@ -0,0 +1,244 @@
+//! Check that MONAD follows the template the fast path was derived from,
+//! reporting exactly where it doesn't.
+
+use super::{
+    alu::{Instr, Operand, Program, MODEL_DIGITS},
+    SubRoutine,
+};
+
+use std::{error, fmt};
+
+/// The instructions of every digit's subroutine. `_` stands for an immediate
+/// that changes between digits.
+const TEMPLATE: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y _",
+    "mul y x", "add z y",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// An instruction differs from the template, or is missing from the
+    /// end of the program.
+    Mismatch {
+        block: usize,
+        instr: usize,
+        expected: &'static str,
+        found: Option<Instr>,
+    },
+    /// z is divided by something other than 1 or 26.
+    Divisor { block: usize, found: i64 },
+    /// A block that pushes onto z could instead leave it unchanged, because
+    /// `x` might equal `w`.
+    PushMayMatch { block: usize, immediate_5: i64 },
+    /// A block pops from z when nothing has been pushed.
+    PopWithoutPush { block: usize },
+    /// The program doesn't have one block per model number digit.
+    BlockCount { found: usize },
+    /// Not every push is matched by a pop.
+    Unbalanced { pushes: usize, pops: usize },
+}
+
+impl TemplateError {
+    /// The index of the offending instruction within the program, if there
+    /// is one.
+    pub fn instr_index(&self) -> Option<usize> {
+        let (block, instr) = match *self {
+            TemplateError::Mismatch { block, instr, .. } => (block, instr),
+            TemplateError::Divisor { block, .. } => (block, 4),
+            TemplateError::PushMayMatch { block, .. } => (block, 5),
+            TemplateError::PopWithoutPush { block } => (block, 4),
+            TemplateError::BlockCount { .. } | TemplateError::Unbalanced { .. } => return None,
+        };
+        Some(block * TEMPLATE.len() + instr)
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::Mismatch {
+                block,
+                instr,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "block {block}, instruction {instr}: expected {expected:?}, "
+                )?;
+                match found {
+                    Some(found) => write!(f, "found {:?}", found.to_string()),
+                    None => write!(f, "found the end of the program"),
+                }
+            }
+            TemplateError::Divisor { block, found } => {
+                write!(f, "block {block}: divides z by {found}, expected 1 or 26")
+            }
+            TemplateError::PushMayMatch { block, immediate_5 } => write!(
+                f,
+                "block {block}: adds {immediate_5} to x before comparing with w, \
+                 expected more than 9"
+            ),
+            TemplateError::PopWithoutPush { block } => {
+                write!(f, "block {block}: divides z by 26 with nothing to pop")
+            }
+            TemplateError::BlockCount { found } => {
+                write!(f, "found {found} blocks, expected {MODEL_DIGITS}")
+            }
+            TemplateError::Unbalanced { pushes, pops } => {
+                write!(f, "{pushes} blocks push onto z but {pops} pop from it")
+            }
+        }
+    }
+}
+
+impl error::Error for TemplateError {}
+
+/// Check every block of `program` against the template, that there is one
+/// block per model number digit and that pushes and pops pair up, returning
+/// the subroutine of each block.
+pub fn validate(program: &Program) -> Result<Vec<SubRoutine>, TemplateError> {
+    let mut subs = Vec::new();
+    let (mut pushes, mut pops) = (0, 0);
+    for (block, instrs) in program.instrs().chunks(TEMPLATE.len()).enumerate() {
+        let mut immediates = Vec::new();
+        for (instr, expected) in TEMPLATE.into_iter().enumerate() {
+            let found = instrs.get(instr);
+            let mismatch = TemplateError::Mismatch {
+                block,
+                instr,
+                expected,
+                found: found.copied(),
+            };
+            let found = found.ok_or(mismatch.clone())?;
+            match expected.strip_suffix('_') {
+                Some(prefix) => match found {
+                    Instr::Bin {
+                        src: Operand::Imm(value),
+                        ..
+                    } if found.to_string().starts_with(prefix) => immediates.push(*value),
+                    _ => return Err(mismatch),
+                },
+                None if found.to_string() == expected => {}
+                None => return Err(mismatch),
+            }
+        }
+
+        let [immediate_4, immediate_5, immediate_15] = immediates[..] else {
+            unreachable!("the template has three immediates");
+        };
+        subs.push(match immediate_4 {
+            1 if immediate_5 <= 9 => {
+                return Err(TemplateError::PushMayMatch { block, immediate_5 });
+            }
+            1 => {
+                pushes += 1;
+                SubRoutine::One {
+                    immediate_15: immediate_15 as isize,
+                }
+            }
+            26 if pops == pushes => return Err(TemplateError::PopWithoutPush { block }),
+            26 => {
+                pops += 1;
+                SubRoutine::Two {
+                    immediate_5: immediate_5 as isize,
+                }
+            }
+            found => return Err(TemplateError::Divisor { block, found }),
+        });
+    }
+    if subs.len() != MODEL_DIGITS {
+        return Err(TemplateError::BlockCount { found: subs.len() });
+    }
+    if pushes != pops {
+        return Err(TemplateError::Unbalanced { pushes, pops });
+    }
+    Ok(subs)
+}
+
+/// The text of a program following the template, with the given `div z`,
+/// `add x` and `add y` immediates for each block.
+#[cfg(test)]
+pub(super) fn program_text(blocks: &[(i64, i64, i64)]) -> String {
+    let mut lines = Vec::new();
+    for &(immediate_4, immediate_5, immediate_15) in blocks {
+        let mut immediates = [immediate_4, immediate_5, immediate_15].into_iter();
+        for line in TEMPLATE {
+            lines.push(match line.strip_suffix('_') {
+                Some(prefix) => format!("{prefix}{}", immediates.next().unwrap()),
+                None => line.to_string(),
+            });
+        }
+    }
+    lines.join("\n")
+}
+
+#[test]
+fn test_validate() {
+    use super::alu::{BinOp, Reg};
+
+    let check = |blocks: &[(i64, i64, i64)]| {
+        validate(&Program::parse(&program_text(blocks)).unwrap()).map(|subs| subs.len())
+    };
+    // Alternately pushing and popping.
+    let balanced = [(1, 12, 7), (26, -3, 15)].repeat(7);
+    let with = |block: usize, sub| {
+        let mut blocks = balanced.clone();
+        blocks[block] = sub;
+        blocks
+    };
+    assert_eq!(check(&balanced), Ok(14));
+    assert_eq!(
+        check(&with(2, (26, -3, 15))),
+        Err(TemplateError::PopWithoutPush { block: 2 })
+    );
+    assert_eq!(
+        check(&with(1, (1, 12, 7))),
+        Err(TemplateError::Unbalanced { pushes: 8, pops: 6 })
+    );
+    assert_eq!(
+        check(&with(1, (2, -3, 15))),
+        Err(TemplateError::Divisor { block: 1, found: 2 })
+    );
+    assert_eq!(
+        check(&with(0, (1, 5, 7))),
+        Err(TemplateError::PushMayMatch {
+            block: 0,
+            immediate_5: 5
+        })
+    );
+    assert_eq!(
+        check(&balanced[..2]),
+        Err(TemplateError::BlockCount { found: 2 })
+    );
+    assert_eq!(
+        check(&[(1, 12, 7), (26, -3, 15)].repeat(8)),
+        Err(TemplateError::BlockCount { found: 16 })
+    );
+
+    let text = program_text(&balanced).replace("add y 25", "add y 24");
+    let err = validate(&Program::parse(&text).unwrap()).unwrap_err();
+    assert_eq!(
+        err,
+        TemplateError::Mismatch {
+            block: 0,
+            instr: 9,
+            expected: "add y 25",
+            found: Some(Instr::Bin {
+                op: BinOp::Add,
+                dst: Reg::Y,
+                src: Operand::Imm(24)
+            })
+        }
+    );
+    assert_eq!(err.instr_index(), Some(9));
+
+    let text = program_text(&[(1, 12, 7)]) + "\ninp w";
+    assert_eq!(
+        validate(&Program::parse(&text).unwrap())
+            .unwrap_err()
+            .to_string(),
+        "block 1, instruction 1: expected \"mul x 0\", found the end of the program"
+    );
+}
@@ -0,0 +1,82 @@
+use crate::error::{AocError, Result};
+use crate::grid::{Direction, Grid};
+
+use aoc_runner_derive::aoc;
+
+/// What is at each location of the sea floor.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LocationState {
+    East,
+    South,
+    Empty,
+}
+
+impl LocationState {
+    /// The character the puzzle uses for the location.
+    pub fn to_char(self) -> char {
+        match self {
+            LocationState::East => '>',
+            LocationState::South => 'v',
+            LocationState::Empty => '.',
+        }
+    }
+}
+
+fn parse_grid(input: &str) -> Result<Grid<LocationState>> {
+    Grid::parse_chars_with(input, |b| match b {
+        b'>' => Ok(LocationState::East),
+        b'v' => Ok(LocationState::South),
+        b'.' => Ok(LocationState::Empty),
+        _ => Err(AocError::new("expected '>', 'v' or '.'")),
+    })
+}
+
+/// Move each herd in turn, east first and then south. Sea cucumbers that
+/// move off one edge reappear on the opposite edge.
+fn tick(grid: &mut Grid<LocationState>) -> usize {
+    let mut num_moves = 0;
+    for (herd, dir) in [
+        (LocationState::East, Direction::Right),
+        (LocationState::South, Direction::Below),
+    ] {
+        let initial_state = grid.clone();
+        for (pos, &location) in initial_state.points_values() {
+            if location != herd {
+                continue;
+            }
+
+            let neighbor = initial_state.step_wrapping(pos, dir);
+            if initial_state[neighbor] == LocationState::Empty {
+                grid[neighbor] = herd;
+                grid[pos] = LocationState::Empty;
+                num_moves += 1;
+            }
+        }
+    }
+    num_moves
+}
+
+#[aoc(day25, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(settle(input)?.0)
+}
+
+/// Move the herds until they stop, returning the first step on which no sea
+/// cucumber moves along with the sea floor from then on. Render the sea
+/// floor with [`crate::render`] to see where they ended up.
+pub fn settle(input: &str) -> Result<(usize, Grid<LocationState>)> {
+    let mut grid = parse_grid(input)?;
+    let mut num_ticks = 0;
+    loop {
+        let num_moves = tick(&mut grid);
+        num_ticks += 1;
+        if num_moves == 0 {
+            break Ok((num_ticks, grid));
+        }
+    }
+}
+
+#[aoc(day25, part2)]
+pub fn part2(_input: &str) -> Result<String> {
+    Ok("unnecesary".to_string())
+}
@@ -0,0 +1,108 @@
+use crate::error::{AocError, Result};
+
+use aoc_runner_derive::aoc;
+
+/// Split the report into lines, verifying that each is a binary number of the
+/// same width.
+fn parse_report(input: &str) -> Result<Vec<&str>> {
+    let lines: Vec<_> = input.split('\n').collect();
+    for line in lines.iter() {
+        if line.len() != lines[0].len() {
+            return Err(AocError::at(input, line, "inconsistent line length"));
+        }
+        if let Some(idx) = line.bytes().position(|b| b != b'0' && b != b'1') {
+            return Err(AocError::at(input, &line[idx..], "expected '0' or '1'"));
+        }
+    }
+    Ok(lines)
+}
+
+#[aoc(day3, part1)]
+pub fn part1(input: &str) -> Result<u64> {
+    let lines = parse_report(input)?;
+    let total_lines = lines.len();
+    let mut cols = vec![0; lines[0].len()];
+    for line in lines {
+        for (col_idx, col_val) in line.bytes().enumerate() {
+            if col_val == b'1' {
+                cols[col_idx] += 1;
+            }
+        }
+    }
+    let mut epsilon = 0;
+    let mut gamma = 0;
+    for col in cols.into_iter() {
+        gamma <<= 1;
+        epsilon <<= 1;
+        if col > (total_lines / 2) {
+            gamma |= 1;
+        } else {
+            epsilon |= 1;
+        }
+    }
+    Ok(gamma * epsilon)
+}
+
+#[aoc(day3, part2)]
+pub fn part2(input: &str) -> Result<u64> {
+    let input = parse_report(input)?;
+    let oxy = oxy_rating(input.clone())?;
+    let co2 = co2_rating(input)?;
+    Ok(oxy * co2)
+}
+
+fn oxy_rating(mut input: Vec<&str>) -> Result<u64> {
+    let num_cols = input[0].len();
+    for col in 0..num_cols {
+        let num_rows = input.len();
+        let ones = count_ones_in_col(&input, col);
+        if ones * 2 >= num_rows {
+            input.retain(|&line| line.as_bytes()[col] == b'1');
+        } else {
+            input.retain(|&line| line.as_bytes()[col] == b'0');
+        }
+        if input.len() == 1 {
+            let mut rating = 0;
+            for digit in input[0].bytes() {
+                rating <<= 1;
+                if digit == b'1' {
+                    rating |= 1;
+                }
+            }
+            return Ok(rating);
+        }
+    }
+    Err(AocError::new("unable to find oxy rating"))
+}
+
+fn co2_rating(mut input: Vec<&str>) -> Result<u64> {
+    let num_cols = input[0].len();
+    for col in 0..num_cols {
+        let num_rows = input.len();
+        let ones = count_ones_in_col(&input, col);
+        if ones * 2 < num_rows {
+            input.retain(|&line| line.as_bytes()[col] == b'1');
+        } else {
+            input.retain(|&line| line.as_bytes()[col] == b'0');
+        }
+        if input.len() == 1 {
+            let mut rating = 0;
+            for digit in input[0].bytes() {
+                rating <<= 1;
+                if digit == b'1' {
+                    rating |= 1;
+                }
+            }
+            return Ok(rating);
+        }
+    }
+    Err(AocError::new("unable to find co2 rating"))
+}
+
+fn count_ones_in_col(input: &[&str], col: usize) -> usize {
+    let mut count = 0;
+    for line in input {
+        count += (line.as_bytes()[col] == b'1') as usize;
+    }
+    count
+}
@@ -1,9 +1,11 @@
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 use std::collections::HashMap;
 
-#[derive(Debug)]
-struct Marked(u32);
-
 const fn bitmask_for_row(row: u8) -> u32 {
     0b11111 << (row * 5)
 }
@@ -26,12 +28,21 @@ struct Board {
     has_won: bool,
 }
 impl Board {
-    fn from_input(input: &str) -> Self {
+    /// Parse the board in `record`, a subslice of the full puzzle `input`.
+    fn from_input(input: &str, record: &str) -> Result<Self> {
         let mut numbers = HashMap::new();
-        for (row_idx, row) in input.split('\n').enumerate() {
-            for (col_idx, number) in row.split_ascii_whitespace().enumerate() {
+        let rows: Vec<_> = record.split('\n').collect();
+        if rows.len() != 5 {
+            return Err(AocError::at(input, record, "expected 5 rows"));
+        }
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            let cols: Vec<_> = row.split_ascii_whitespace().collect();
+            if cols.len() != 5 {
+                return Err(AocError::at(input, row, "expected 5 columns"));
+            }
+            for (col_idx, number) in cols.into_iter().enumerate() {
                 numbers.insert(
-                    number.parse().unwrap(),
+                    parse::parse(input, number)?,
                     RowCol {
                         row_idx: row_idx as u8,
                         col_idx: col_idx as u8,
@@ -39,11 +50,11 @@ impl Board {
                 );
             }
         }
-        Board {
+        Ok(Board {
             numbers,
             marked: 0,
             has_won: false,
-        }
+        })
     }
 
     fn is_marked(&self, pos: RowCol) -> bool {
@@ -71,24 +82,29 @@ impl Board {
     }
 }
 
-#[aoc(day4, part1)]
-pub fn part1(input: &str) -> u64 {
+fn parse_game(input: &str) -> Result<(Vec<u8>, Vec<Board>)> {
     let mut records = input.split("\n\n");
-    let drawn_numbers = records
-        .next()
-        .unwrap()
+    let drawn_numbers = parse::next(input, &mut records, input, "drawn numbers")?
         .trim()
         .split(',')
-        .map(|s| -> u8 { s.parse().unwrap() });
+        .map(|s| parse::parse(input, s))
+        .collect::<Result<_>>()?;
+    let boards = records
+        .map(|record| Board::from_input(input, record))
+        .collect::<Result<_>>()?;
+    Ok((drawn_numbers, boards))
+}
 
-    let mut boards: Vec<_> = records.map(Board::from_input).collect();
+#[aoc(day4, part1)]
+pub fn part1(input: &str) -> Result<u64> {
+    let (drawn_numbers, mut boards) = parse_game(input)?;
 
     for number in drawn_numbers {
         for board in boards.iter_mut() {
             if let Some(pos) = board.numbers.get(&number).copied() {
                 board.mark(pos);
                 if board.has_won() {
-                    return board
+                    return Ok(board
                         .numbers
                         .iter()
                         .filter_map(|(&n, &pos)| {
@@ -99,25 +115,17 @@ pub fn part1(input: &str) -> u64 {
                             }
                         })
                         .sum::<u64>()
-                        * number as u64;
+                        * number as u64);
                 }
             }
         }
     }
-    panic!("no winner");
+    Err(AocError::new("no winner"))
 }
 
 #[aoc(day4, part2)]
-pub fn part2(input: &str) -> u64 {
-    let mut records = input.split("\n\n");
-    let drawn_numbers = records
-        .next()
-        .unwrap()
-        .trim()
-        .split(',')
-        .map(|s| -> u8 { s.parse().unwrap() });
-
-    let mut boards: Vec<_> = records.map(Board::from_input).collect();
+pub fn part2(input: &str) -> Result<u64> {
+    let (drawn_numbers, mut boards) = parse_game(input)?;
 
     for number in drawn_numbers {
         let num_boards = boards.len();
@@ -125,7 +133,7 @@ pub fn part2(input: &str) -> u64 {
             if let Some(pos) = board.numbers.get(&number).copied() {
                 board.mark(pos);
                 if num_boards == 1 && board.has_won() {
-                    return board
+                    return Ok(board
                         .numbers
                         .iter()
                         .filter_map(|(&n, &pos)| {
@@ -136,14 +144,11 @@ pub fn part2(input: &str) -> u64 {
                             }
                         })
                         .sum::<u64>()
-                        * number as u64;
+                        * number as u64);
                 }
             }
         }
-        boards = boards
-            .into_iter()
-            .filter(|board| !board.has_won())
-            .collect();
+        boards.retain(|board| !board.has_won());
     }
-    panic!("no loser")
+    Err(AocError::new("no loser"))
 }
@@ -1,9 +1,10 @@
 use std::{
     cmp::{max, Ordering},
     collections::HashMap,
-    num::ParseIntError,
 };
 
+use crate::{error::Result, parse};
+
 use aoc_runner_derive::aoc;
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
@@ -11,13 +12,12 @@ struct XY {
     x: u32,
     y: u32,
 }
-impl std::str::FromStr for XY {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').unwrap();
+impl XY {
+    fn from_input(input: &str, s: &str) -> Result<Self> {
+        let (x, y) = parse::split_once(input, s, ",")?;
         Ok(XY {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: parse::parse(input, x)?,
+            y: parse::parse(input, y)?,
         })
     }
 }
@@ -26,17 +26,15 @@ struct Line {
     start: XY,
     end: XY,
 }
-impl std::str::FromStr for Line {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once(" -> ").unwrap();
+impl Line {
+    fn from_input(input: &str, s: &str) -> Result<Self> {
+        let (start, end) = parse::split_once(input, s, " -> ")?;
         Ok(Line {
-            start: start.parse()?,
-            end: end.parse()?,
+            start: XY::from_input(input, start)?,
+            end: XY::from_input(input, end)?,
         })
     }
-}
-impl Line {
+
     fn is_horizontal(&self) -> bool {
         self.start.y == self.end.y
     }
@@ -95,22 +93,23 @@ impl Iterator for PointIter {
 }
 
 #[aoc(day5, part1)]
-pub fn part1(input: &str) -> usize {
-    let lines: Vec<Line> = input
-        .split('\n')
-        .map(|line| line.parse().unwrap())
-        .filter(|line: &Line| line.is_horizontal() || line.is_vertical())
-        .collect();
-    num_overlapping_points(&lines)
+pub fn part1(input: &str) -> Result<usize> {
+    let mut lines = parse_lines(input)?;
+    lines.retain(|line| line.is_horizontal() || line.is_vertical());
+    Ok(num_overlapping_points(&lines))
 }
 
 #[aoc(day5, part2)]
-pub fn part2(input: &str) -> usize {
-    let lines: Vec<Line> = input
+pub fn part2(input: &str) -> Result<usize> {
+    let lines = parse_lines(input)?;
+    Ok(num_overlapping_points(&lines))
+}
+
+fn parse_lines(input: &str) -> Result<Vec<Line>> {
+    input
         .split('\n')
-        .map(|line| line.parse().unwrap())
-        .collect();
-    num_overlapping_points(&lines)
+        .map(|line| Line::from_input(input, line))
+        .collect()
 }
 
 fn num_overlapping_points(lines: &[Line]) -> usize {
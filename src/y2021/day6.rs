@@ -0,0 +1,34 @@
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
+use aoc_runner_derive::aoc;
+
+#[aoc(day6, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    simulation(input, 80)
+}
+
+#[aoc(day6, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    simulation(input, 256)
+}
+
+fn simulation(input: &str, num_days: usize) -> Result<usize> {
+    let mut fish_states = [0; 9];
+    for s in input.split(',') {
+        let fish: usize = parse::parse(input, s)?;
+        if fish >= fish_states.len() {
+            return Err(AocError::at(input, s, "timer must be between 0 and 8"));
+        }
+        fish_states[fish] += 1;
+    }
+    for _day in 0..num_days {
+        fish_states.rotate_left(1);
+        let fish_created = fish_states[8];
+        fish_states[6] += fish_states[8];
+        fish_states[8] = fish_created;
+    }
+    Ok(fish_states.iter().sum())
+}
@@ -0,0 +1,30 @@
+use crate::{error::Result, parse};
+
+use aoc_runner_derive::aoc;
+
+fn parse_positions(input: &str) -> Result<Vec<usize>> {
+    input.split(',').map(|s| parse::parse(input, s)).collect()
+}
+
+#[aoc(day7, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    let mut pos = parse_positions(input)?;
+    pos.sort_unstable();
+    let median = pos[pos.len() / 2];
+    Ok(pos.iter().map(|&s| s.abs_diff(median)).sum())
+}
+
+#[aoc(day7, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    let pos = parse_positions(input)?;
+    let mean: usize = pos.iter().sum::<usize>() / pos.len();
+    Ok(pos
+        .iter()
+        .map(|&s| s.abs_diff(mean))
+        .map(p2_fuel_for_distance)
+        .sum())
+}
+
+fn p2_fuel_for_distance(d: usize) -> usize {
+    (d * (d + 1)) / 2
+}
@@ -1,28 +1,62 @@
+use crate::{
+    error::{AocError, Result},
+    parse,
+};
+
 use aoc_runner_derive::aoc;
 
 #[aoc(day8, part1)]
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> Result<usize> {
+    let mut count = 0;
+    for line in input.split('\n') {
+        let (_, output) = parse_entry(input, line)?;
+        count += output
+            .into_iter()
+            .filter(|s| [2usize, 3, 4, 7].contains(&s.len()))
+            .count();
+    }
+    Ok(count)
+}
+
+#[aoc(day8, part2)]
+pub fn part2(input: &str) -> Result<usize> {
     input
         .split('\n')
-        .flat_map(|line| {
-            let (_, output) = line.split_once(" | ").unwrap();
-            output.split(' ')
-        })
-        .filter(|s| [2usize, 3, 4, 7].contains(&s.len()))
-        .count()
+        .map(|line| decode_entry(input, line))
+        .sum()
 }
 
-#[aoc(day8, part2)]
-pub fn part2(input: &str) -> usize {
-    input.split('\n').map(decode_entry).sum()
+/// Split an entry into its ten signal patterns and four output values,
+/// verifying that each only uses the segments 'a' through 'g'.
+fn parse_entry<'a>(input: &str, line: &'a str) -> Result<(Vec<&'a str>, Vec<&'a str>)> {
+    let (signals, output) = parse::split_once(input, line, " | ")?;
+    let signals: Vec<_> = signals.split(' ').collect();
+    let output: Vec<_> = output.split(' ').collect();
+    if signals.len() != 10 {
+        return Err(AocError::at(input, line, "expected 10 signal patterns"));
+    }
+    if output.len() != 4 {
+        return Err(AocError::at(input, line, "expected 4 output values"));
+    }
+    for pattern in signals.iter().chain(output.iter()) {
+        if let Some(idx) = pattern.bytes().position(|b| !(b'a'..=b'g').contains(&b)) {
+            return Err(AocError::at(
+                input,
+                &pattern[idx..],
+                "expected a segment 'a'..='g'",
+            ));
+        }
+    }
+    Ok((signals, output))
 }
 
-fn decode_entry(input: &str) -> usize {
-    let (signals, output) = input.split_once(" | ").unwrap();
-    let mapping = Mapping::new(signals);
-    output.split(' ').fold(0, |accum, output| {
+fn decode_entry(input: &str, line: &str) -> Result<usize> {
+    let (signals, output) = parse_entry(input, line)?;
+    let mapping = Mapping::new(&signals)
+        .ok_or_else(|| AocError::at(input, line, "signal patterns are inconsistent"))?;
+    Ok(output.into_iter().fold(0, |accum, output| {
         accum * 10 + mapping.decode_output(output) as usize
-    })
+    }))
 }
 
 const A_SEGMENT: u8 = 1 << 0;
@@ -43,13 +77,15 @@ struct Mapping {
     signal_to_output: [u8; 7],
 }
 impl Mapping {
-    fn new(signals: &str) -> Self {
+    /// Deduce the wiring from the ten signal patterns. Returns `None` if the
+    /// patterns don't describe the digits 0-9.
+    fn new(signals: &[&str]) -> Option<Self> {
         let mut signal_to_output = [0; 7];
-        let signals: Vec<_> = signals.split(' ').map(|s| s.as_bytes()).collect();
+        let signals: Vec<_> = signals.iter().map(|s| s.as_bytes()).collect();
         // find 2 digits, that's '1'. one of the two letters will appear in 8
         // outputs that' s the top half (C_SEGMENT), the other will appear in 9
         // that's the bottom half (F_SEGMENT).
-        let one = signals.iter().find(|s| s.len() == 2).unwrap();
+        let one = signals.iter().find(|s| s.len() == 2)?;
         match signals.iter().filter(|s| s.contains(&one[0])).count() {
             8 => {
                 signal_to_output[char_to_idx(one[0])] = C_SEGMENT;
@@ -59,21 +95,21 @@ impl Mapping {
                 signal_to_output[char_to_idx(one[0])] = F_SEGMENT;
                 signal_to_output[char_to_idx(one[1])] = C_SEGMENT;
             }
-            _ => panic!("too many signals contain {:?}", one),
+            _ => return None,
         };
         // find 3 digits, that's '7'. it will contain two of the same letters as
         // '1' above, the third letter is the top (A_SEGMENT).
-        let seven = signals.iter().find(|s| s.len() == 3).unwrap();
-        let top = *seven.iter().find(|b| !one.contains(b)).unwrap();
+        let seven = signals.iter().find(|s| s.len() == 3)?;
+        let top = *seven.iter().find(|b| !one.contains(b))?;
         signal_to_output[char_to_idx(top)] = A_SEGMENT;
         // find 4 digits, that's '4'. it will contain two of the same letters as
         // '1' above, of the other 2 letters one is contained in 6 outputs and
         // is the top left (B_SEGMENT); the one contained in 7 outputs is the
         // middle (D_SEGMENT).
-        let four = signals.iter().find(|s| s.len() == 4).unwrap();
+        let four = signals.iter().find(|s| s.len() == 4)?;
         let mut four = four.iter().filter(|b| !one.contains(b));
-        let first_four = *four.next().unwrap();
-        let second_four = *four.next().unwrap();
+        let first_four = *four.next()?;
+        let second_four = *four.next()?;
         match signals.iter().filter(|s| s.contains(&first_four)).count() {
             6 => {
                 signal_to_output[char_to_idx(first_four)] = B_SEGMENT;
@@ -83,7 +119,7 @@ impl Mapping {
                 signal_to_output[char_to_idx(first_four)] = D_SEGMENT;
                 signal_to_output[char_to_idx(second_four)] = B_SEGMENT;
             }
-            _ => panic!("too many signals contain {:?}", first_four),
+            _ => return None,
         };
         // there are two remaining letters. the one contained in 4 outputs is
         // the bottom left (E_SEGMENT); the one contained in 7 outputs is the
@@ -91,8 +127,8 @@ impl Mapping {
         let mut rem = b"abcdefg"
             .iter()
             .filter(|b| ![top, one[0], one[1], first_four, second_four].contains(b));
-        let first = *rem.next().unwrap();
-        let second = *rem.next().unwrap();
+        let first = *rem.next()?;
+        let second = *rem.next()?;
         match signals.iter().filter(|s| s.contains(&first)).count() {
             4 => {
                 signal_to_output[char_to_idx(first)] = E_SEGMENT;
@@ -102,9 +138,9 @@ impl Mapping {
                 signal_to_output[char_to_idx(first)] = G_SEGMENT;
                 signal_to_output[char_to_idx(second)] = E_SEGMENT;
             }
-            _ => panic!("too many signals contain {:?}", first),
+            _ => return None,
         };
-        Mapping { signal_to_output }
+        Some(Mapping { signal_to_output })
     }
 
     // Return the number 0..=9 corresponding to a series of letters contained in
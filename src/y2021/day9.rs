@@ -0,0 +1,55 @@
+use crate::{
+    error::Result,
+    grid::{Grid, X, Y},
+};
+use std::collections::HashSet;
+
+use aoc_runner_derive::aoc;
+
+#[aoc(day9, part1)]
+pub fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::parse_digits(input)?;
+    Ok(grid
+        .points_values()
+        .filter(|&((x, y), _)| is_low_point(&grid, x, y))
+        .map(|(_, &level)| level as usize + 1)
+        .sum())
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &str) -> Result<usize> {
+    let grid = Grid::parse_digits(input)?;
+    let mut basin_sizes: Vec<_> = grid
+        .points_values()
+        .filter(|&((x, y), _)| is_low_point(&grid, x, y))
+        .map(|((x, y), _)| find_basin_size(&grid, x, y))
+        .collect();
+    basin_sizes.sort_unstable_by_key(|k| std::cmp::Reverse(*k));
+    Ok(basin_sizes.iter().take(3).product())
+}
+
+/// Whether every adjacent location is higher, which a location with no
+/// neighbors trivially is.
+fn is_low_point(grid: &Grid<u8>, x: X, y: Y) -> bool {
+    let level = grid[(x, y)];
+    grid.neighbors_4(x, y).all(|pos| level < grid[pos])
+}
+
+fn find_basin_size(grid: &Grid<u8>, x: X, y: Y) -> usize {
+    fn _find_basin_size(grid: &Grid<u8>, x: X, y: Y, seen: &mut HashSet<(X, Y)>) -> usize {
+        if seen.contains(&(x, y)) {
+            return 0;
+        }
+        seen.insert((x, y));
+        let level = grid[(x, y)];
+        let mut basin_size = 1;
+        for (adj_x, adj_y) in grid.neighbors_4(x, y) {
+            let adj_level = grid[(adj_x, adj_y)];
+            if adj_level != 9 && adj_level > level {
+                basin_size += _find_basin_size(grid, adj_x, adj_y, seen);
+            }
+        }
+        basin_size
+    }
+    _find_basin_size(grid, x, y, &mut HashSet::new())
+}
@@ -0,0 +1,80 @@
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::registry::Solution;
+
+pub(crate) const SOLUTIONS: &[Solution] = &[
+    solution!(2021, 1, 1, day1::part1),
+    solution!(2021, 1, 2, day1::part2),
+    solution!(2021, 2, 1, day2::part1),
+    solution!(2021, 2, 2, day2::part2),
+    solution!(2021, 3, 1, day3::part1),
+    solution!(2021, 3, 2, day3::part2),
+    solution!(2021, 4, 1, day4::part1),
+    solution!(2021, 4, 2, day4::part2),
+    solution!(2021, 5, 1, day5::part1),
+    solution!(2021, 5, 2, day5::part2),
+    solution!(2021, 6, 1, day6::part1),
+    solution!(2021, 6, 2, day6::part2),
+    solution!(2021, 7, 1, day7::part1),
+    solution!(2021, 7, 2, day7::part2),
+    solution!(2021, 8, 1, day8::part1),
+    solution!(2021, 8, 2, day8::part2),
+    solution!(2021, 9, 1, day9::part1),
+    solution!(2021, 9, 2, day9::part2),
+    solution!(2021, 10, 1, day10::part1),
+    solution!(2021, 10, 2, day10::part2),
+    solution!(2021, 11, 1, day11::part1),
+    solution!(2021, 11, 2, day11::part2),
+    solution!(2021, 12, 1, day12::part1),
+    solution!(2021, 12, 2, day12::part2),
+    solution!(2021, 13, 1, day13::part1),
+    solution!(2021, 13, 2, day13::part2),
+    solution!(2021, 14, 1, day14::part1),
+    solution!(2021, 14, 2, day14::part2),
+    solution!(2021, 15, 1, day15::part1),
+    solution!(2021, 15, 2, day15::part2),
+    solution!(2021, 16, 1, day16::part1),
+    solution!(2021, 16, 2, day16::part2),
+    solution!(2021, 17, 1, day17::part1),
+    solution!(2021, 17, 2, day17::part2),
+    solution!(2021, 18, 1, day18::part1),
+    solution!(2021, 18, 2, day18::part2),
+    solution!(2021, 19, 1, day19::part1),
+    solution!(2021, 19, 2, day19::part2),
+    solution!(2021, 20, 1, day20::part1),
+    solution!(2021, 20, 2, day20::part2),
+    solution!(2021, 21, 1, day21::part1),
+    solution!(2021, 21, 2, day21::part2),
+    solution!(2021, 22, 1, day22::part1),
+    solution!(2021, 22, 2, day22::part2),
+    solution!(2021, 23, 1, day23::part1),
+    solution!(2021, 23, 2, day23::part2),
+    solution!(2021, 24, 1, day24::part1),
+    solution!(2021, 24, 2, day24::part2),
+    solution!(2021, 25, 1, day25::part1),
+    solution!(2021, 25, 2, day25::part2),
+];
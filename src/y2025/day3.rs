@@ -0,0 +1,56 @@
+use crate::error::{AocError, Result};
+
+pub fn part1(input: &str) -> Result<u64> {
+    parse_banks(input)?
+        .into_iter()
+        .map(|bank| max_joltage(input, bank, 2))
+        .sum()
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    parse_banks(input)?
+        .into_iter()
+        .map(|bank| max_joltage(input, bank, 12))
+        .sum()
+}
+
+fn parse_banks(input: &str) -> Result<Vec<&str>> {
+    input
+        .split('\n')
+        .map(
+            |bank| match bank.bytes().position(|b| !(b'1'..=b'9').contains(&b)) {
+                Some(idx) => Err(AocError::at(input, &bank[idx..], "expected a digit 1-9")),
+                None => Ok(bank),
+            },
+        )
+        .collect()
+}
+
+/// Pick `num_batteries` digits from the bank, keeping their order, such that
+/// the number they form is as large as possible.
+fn max_joltage(input: &str, bank: &str, num_batteries: usize) -> Result<u64> {
+    if bank.len() < num_batteries {
+        return Err(AocError::at(
+            input,
+            bank,
+            format!("bank needs at least {num_batteries} batteries"),
+        ));
+    }
+    let bank = bank.as_bytes();
+    let mut joltage = 0;
+    let mut start = 0;
+    for remaining in (0..num_batteries).rev() {
+        // Greedily take the largest digit that still leaves enough batteries
+        // to the right to fill the remaining slots. Ties go to the leftmost
+        // digit so that more candidates remain for later slots.
+        let (idx, &digit) = bank[start..bank.len() - remaining]
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, &b)| b)
+            .unwrap();
+        start += idx + 1;
+        joltage = joltage * 10 + (digit - b'0') as u64;
+    }
+    Ok(joltage)
+}
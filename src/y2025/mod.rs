@@ -0,0 +1,11 @@
+// aoc-runner only supports a single year per crate and that year is 2021, so
+// solutions here are not annotated with `#[aoc]` and are only reachable through
+// the registry.
+pub mod day3;
+
+use crate::registry::Solution;
+
+pub(crate) const SOLUTIONS: &[Solution] = &[
+    solution!(2025, 3, 1, day3::part1),
+    solution!(2025, 3, 2, day3::part2),
+];